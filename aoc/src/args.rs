@@ -0,0 +1,93 @@
+use util::*;
+
+/// Minimal parser for `[positional...] [--flag value...]` arguments
+///
+/// Commands take the values they understand and `finish` rejects anything
+/// left over.
+#[derive(Debug, Default)]
+pub struct Args {
+    positional: VecDeque<String>,
+    flags: HashMap<String, Option<String>>,
+}
+
+impl Args {
+    pub fn parse(args: impl Iterator<Item = String>) -> Self {
+        let mut parsed = Self::default();
+        let mut args = args.peekable();
+
+        while let Some(arg) = args.next() {
+            if arg.starts_with("--") {
+                let value = args.next_if(|v| !v.starts_with("--"));
+
+                parsed.flags.insert(arg, value);
+            } else {
+                parsed.positional.push_back(arg);
+            }
+        }
+
+        parsed
+    }
+
+    pub fn positional(&mut self, name: &str) -> Result<String, Error> {
+        self.positional
+            .pop_front()
+            .with_context(|| format!("Missing <{name}>"))
+    }
+
+    /// Value of a `--name <value>` flag
+    pub fn flag<T>(&mut self, name: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: Into<Error>,
+    {
+        match self.flags.remove(name) {
+            Some(Some(value)) => value
+                .parse()
+                .map(Some)
+                .map_err(Into::into)
+                .with_context(|| format!("Invalid value for {name}: {value}")),
+            Some(None) => bail!("Missing value for {name}"),
+            None => Ok(None),
+        }
+    }
+
+    pub fn finish(self) -> Result<(), Error> {
+        if let Some(arg) = self.positional.front() {
+            bail!("Unexpected argument: {arg}");
+        }
+
+        if let Some(flag) = self.flags.keys().next() {
+            bail!("Unexpected flag: {flag}");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(s: &str) -> Args {
+        Args::parse(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn parse() -> Result<(), Error> {
+        let mut args = args("1..=5 --runs 3 --save out.json");
+
+        assert_eq!(args.positional("days")?, "1..=5");
+        assert_eq!(args.flag::<usize>("--runs")?, Some(3));
+        assert_eq!(args.flag::<String>("--save")?.as_deref(), Some("out.json"));
+        assert!(args.positional("extra").is_err());
+
+        args.finish()
+    }
+
+    #[test]
+    fn leftovers() {
+        assert!(args("1 2").finish().is_err());
+        assert!(args("--runs 3").finish().is_err());
+        assert!(args("--runs x").flag::<usize>("--runs").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    any::Any,
+    panic,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use util::*;
+
+use crate::{args::Args, days};
+
+enum Outcome {
+    Solved(Report),
+    Failed(String),
+    Panicked(String),
+}
+
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic".into()
+    }
+}
+
+fn solve_file(day: &days::Day, path: &Path) -> Outcome {
+    let input = match input::read(path) {
+        Ok(input) => input,
+        Err(err) => return Outcome::Failed(format!("{err:#}")),
+    };
+
+    match panic::catch_unwind(|| (day.solve)(&input.text)) {
+        Ok(Ok(report)) => Outcome::Solved(report),
+        Ok(Err(err)) => Outcome::Failed(format!("{err:#}")),
+        Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+    }
+}
+
+/// Solve every file on `jobs` threads, returning outcomes in file order
+fn solve_all(day: &days::Day, files: &[PathBuf], jobs: usize) -> Vec<Outcome> {
+    let next = AtomicUsize::new(0);
+    let mut outcomes = thread::scope(|s| {
+        let workers = (0..jobs)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut outcomes = Vec::new();
+
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = files.get(i) else {
+                            break outcomes;
+                        };
+
+                        outcomes.push((i, solve_file(day, path)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_default())
+            .collect::<Vec<_>>()
+    });
+
+    outcomes.sort_by_key(|(i, _)| *i);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+fn millis(d: Duration) -> String {
+    format!("{:.3} ms", d.as_secs_f64() * 1000.0)
+}
+
+// Multi-line answers (e.g., day 10) don't fit in a table cell
+fn cell(answer: &str) -> String {
+    match answer.lines().count() {
+        0 | 1 => answer.to_string(),
+        n => format!("<{n} lines>"),
+    }
+}
+
+pub fn batch(mut args: Args) -> Result<(), Error> {
+    let day = days::get(args.positional("day")?.parse().context("Invalid day")?)?;
+    let dir = PathBuf::from(args.positional("dir")?);
+    let default_jobs = thread::available_parallelism().map_or(1, |n| n.get());
+    let jobs = args.flag("--jobs")?.unwrap_or(default_jobs).max(1);
+
+    args.finish()?;
+
+    let files = input::files(&dir)?;
+
+    if files.is_empty() {
+        bail!("No inputs in {}", dir.display());
+    }
+
+    // Panics are reported in the table instead of on stderr
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let outcomes = solve_all(day, &files, jobs);
+    panic::set_hook(hook);
+
+    let width = files
+        .iter()
+        .filter_map(|path| path.file_name())
+        .map(|name| name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Input".len());
+
+    println!(
+        "{:<width$}  {:<7} {:>12} {:>12} {:>12}  Answers",
+        "Input", "Status", "Parse", "Part 1", "Part 2"
+    );
+
+    let mut failed = 0;
+
+    for (path, outcome) in files.iter().zip(&outcomes) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        match outcome {
+            Outcome::Solved(report) => println!(
+                "{name:<width$}  {:<7} {:>12} {:>12} {:>12}  {} / {}",
+                "ok",
+                millis(report.timings.parse),
+                millis(report.timings.part_1),
+                millis(report.timings.part_2),
+                cell(&report.answers.part_1),
+                cell(&report.answers.part_2),
+            ),
+            Outcome::Failed(err) => {
+                failed += 1;
+                println!("{name:<width$}  {:<7} {err}", "error");
+            }
+            Outcome::Panicked(msg) => {
+                failed += 1;
+                println!("{name:<width$}  {:<7} {msg}", "panic");
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{failed} of {} inputs failed", files.len());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn failing(input: &str) -> Result<Report, Error> {
+        match input {
+            "panic" => panic!("boom"),
+            "error" => bail!("bad input"),
+            _ => solve::<day_01::Day>(input),
+        }
+    }
+
+    #[test]
+    fn outcomes() -> Result<(), Error> {
+        let dir = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+        let day = days::Day {
+            day: 1,
+            solve: failing,
+            ..*days::get(1)?
+        };
+
+        std::fs::create_dir_all(&dir)?;
+
+        for (name, text) in [
+            ("a.txt", "1\n2\n\n3"),
+            ("b.txt", "panic"),
+            ("c.txt", "error"),
+        ] {
+            std::fs::write(dir.join(name), text)?;
+        }
+
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        let outcomes = solve_all(&day, &input::files(&dir)?, 2);
+        panic::set_hook(hook);
+        std::fs::remove_dir_all(&dir)?;
+
+        assert!(matches!(&outcomes[0], Outcome::Solved(r) if r.answers.part_1 == "3"));
+        assert!(matches!(&outcomes[1], Outcome::Panicked(msg) if msg == "boom"));
+        assert!(matches!(&outcomes[2], Outcome::Failed(msg) if msg == "bad input"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn cells() {
+        assert_eq!(cell("42"), "42");
+        assert_eq!(cell("#.\n.#"), "<2 lines>");
+    }
+}
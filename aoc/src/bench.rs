@@ -0,0 +1,208 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use util::*;
+
+use crate::{args::Args, days, read_input};
+
+// Phases faster than this are dominated by noise and never flagged
+static MIN_DELTA: Duration = Duration::from_micros(100);
+
+/// Wall time of each phase in milliseconds
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Phases {
+    parse_ms: f64,
+    part_1_ms: f64,
+    part_2_ms: f64,
+}
+
+/// Saved timings keyed by day
+type Baseline = BTreeMap<u8, Phases>;
+
+impl From<Timings> for Phases {
+    fn from(timings: Timings) -> Self {
+        Self {
+            parse_ms: timings.parse.as_secs_f64() * 1000.0,
+            part_1_ms: timings.part_1.as_secs_f64() * 1000.0,
+            part_2_ms: timings.part_2.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+impl Phases {
+    fn iter(&self) -> [(&'static str, f64); 3] {
+        [
+            ("parse", self.parse_ms),
+            ("part 1", self.part_1_ms),
+            ("part 2", self.part_2_ms),
+        ]
+    }
+
+    // Keep the fastest of several runs; slower runs only add noise
+    fn min(self, other: Self) -> Self {
+        Self {
+            parse_ms: self.parse_ms.min(other.parse_ms),
+            part_1_ms: self.part_1_ms.min(other.part_1_ms),
+            part_2_ms: self.part_2_ms.min(other.part_2_ms),
+        }
+    }
+}
+
+struct Regression {
+    day: u8,
+    phase: &'static str,
+    baseline: f64,
+    current: f64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Day {:02} {} regressed: {:.3} ms -> {:.3} ms ({:+.0}%)",
+            self.day,
+            self.phase,
+            self.baseline,
+            self.current,
+            change(self.baseline, self.current),
+        )
+    }
+}
+
+fn change(baseline: f64, current: f64) -> f64 {
+    (current - baseline) / baseline * 100.0
+}
+
+fn is_regression(baseline: f64, current: f64, threshold: f64) -> bool {
+    current - baseline > MIN_DELTA.as_secs_f64() * 1000.0 && change(baseline, current) > threshold
+}
+
+fn measure(
+    solve: fn(&str) -> Result<Report, Error>,
+    input: &str,
+    runs: usize,
+) -> Result<Phases, Error> {
+    let mut phases = Phases::from(solve(input)?.timings);
+
+    for _ in 1..runs {
+        phases = phases.min(solve(input)?.timings.into());
+    }
+
+    Ok(phases)
+}
+
+fn read_baseline(path: &Path) -> Result<Baseline, Error> {
+    let baseline = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+
+    serde_json::from_str(&baseline).with_context(|| format!("Invalid baseline {}", path.display()))
+}
+
+pub fn bench(mut args: Args) -> Result<(), Error> {
+    let days = days::select(&args.positional("days")?)?;
+    let inputs = args
+        .flag("--inputs")?
+        .unwrap_or_else(|| PathBuf::from("inputs"));
+    let runs = args.flag("--runs")?.unwrap_or(1usize).max(1);
+    let threshold = args.flag("--threshold")?.unwrap_or(10.0);
+    let save: Option<PathBuf> = args.flag("--save")?;
+    let baseline = args
+        .flag::<PathBuf>("--baseline")?
+        .map(|path| read_baseline(&path))
+        .transpose()?;
+
+    args.finish()?;
+
+    let mut results = Baseline::new();
+    let mut regressions = Vec::new();
+    let mut failed = 0;
+
+    println!(
+        "{:<5}{:>16}{:>16}{:>16}",
+        "Day", "Parse", "Part 1", "Part 2"
+    );
+
+    for day in days {
+        print!("{:<5}", format!("{:02}", day.day));
+
+        let phases = read_input(&inputs, day.day).and_then(|i| measure(day.solve, &i, runs));
+        let phases = match phases {
+            Ok(phases) => phases,
+            Err(err) => {
+                failed += 1;
+                println!("Error: {err:#}");
+                continue;
+            }
+        };
+
+        let previous = baseline.as_ref().and_then(|b| b.get(&day.day));
+
+        for (i, (phase, current)) in phases.iter().into_iter().enumerate() {
+            let cell = match previous.map(|p| p.iter()[i].1) {
+                Some(baseline) if is_regression(baseline, current, threshold) => {
+                    regressions.push(Regression {
+                        day: day.day,
+                        phase,
+                        baseline,
+                        current,
+                    });
+
+                    format!("{current:.3} ms!")
+                }
+                _ => format!("{current:.3} ms"),
+            };
+
+            print!("{cell:>16}");
+        }
+
+        println!();
+        results.insert(day.day, phases);
+    }
+
+    if let Some(path) = save {
+        // Merge into an existing baseline so a subset of days can be refreshed
+        let mut saved = if path.exists() {
+            read_baseline(&path)?
+        } else {
+            Baseline::new()
+        };
+
+        saved.extend(results);
+        fs::write(&path, serde_json::to_string_pretty(&saved)?)
+            .with_context(|| format!("Failed to write baseline {}", path.display()))?;
+    }
+
+    if !regressions.is_empty() {
+        println!();
+
+        for regression in &regressions {
+            println!("{regression}");
+        }
+
+        bail!("{} phases regressed beyond {threshold}%", regressions.len());
+    }
+
+    if failed > 0 {
+        bail!("{failed} days failed");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn regression() {
+        assert!(super::is_regression(100.0, 120.0, 10.0));
+        assert!(!super::is_regression(100.0, 105.0, 10.0));
+        assert!(!super::is_regression(100.0, 50.0, 10.0));
+
+        // Below the noise floor
+        assert!(!super::is_regression(0.01, 0.05, 10.0));
+    }
+}
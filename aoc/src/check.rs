@@ -0,0 +1,219 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use util::*;
+
+use crate::{args::Args, days, read_input};
+
+/// Expected answer; integers are accepted for convenience
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{}", normalize(s)),
+        }
+    }
+}
+
+// Multi-line answers are compared without surrounding whitespace on any line
+fn normalize(answer: &str) -> String {
+    answer
+        .trim()
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+struct Expected {
+    #[serde(rename = "part-1")]
+    part_1: Option<Answer>,
+    #[serde(rename = "part-2")]
+    part_2: Option<Answer>,
+}
+
+/// Contents of `answers.toml`, keyed by `day-NN` tables
+#[derive(Debug, Default)]
+struct KnownAnswers(BTreeMap<u8, Expected>);
+
+impl FromStr for KnownAnswers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str::<BTreeMap<String, Expected>>(s)?
+            .into_iter()
+            .map(|(key, expected)| {
+                let day = key
+                    .strip_prefix("day-")
+                    .and_then(|day| day.parse().ok())
+                    .with_context(|| format!("Invalid day: {key}"))?;
+
+                Ok((day, expected))
+            })
+            .collect::<Result<_, Error>>()
+            .map(KnownAnswers)
+    }
+}
+
+impl KnownAnswers {
+    fn read(path: &Path) -> Result<Self, Error> {
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .parse()
+            .with_context(|| format!("Invalid answers {}", path.display()))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+}
+
+impl Status {
+    fn new(expected: Option<&Answer>, actual: &str) -> Self {
+        let actual = normalize(actual);
+
+        match expected.map(Answer::to_string) {
+            Some(expected) if expected == actual => Status::Pass,
+            Some(expected) => Status::Fail { expected, actual },
+            None => Status::Missing,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail { .. } => "FAIL",
+            Status::Missing => "missing",
+        }
+    }
+}
+
+pub fn check(mut args: Args) -> Result<(), Error> {
+    let days = days::select(&args.positional("days")?)?;
+    let inputs = args
+        .flag("--inputs")?
+        .unwrap_or_else(|| PathBuf::from("inputs"));
+    let answers = args
+        .flag("--answers")?
+        .unwrap_or_else(|| PathBuf::from("answers.toml"));
+
+    args.finish()?;
+
+    let known = KnownAnswers::read(&answers)?;
+    let mut failures = Vec::new();
+
+    println!("Day  Part 1    Part 2");
+
+    for day in days {
+        let expected = known.0.get(&day.day).cloned().unwrap_or_default();
+        let report = match read_input(&inputs, day.day).and_then(|i| (day.solve)(&i)) {
+            Ok(report) => report,
+            Err(err) => {
+                println!("{:02}   error     error", day.day);
+                failures.push(format!("Day {:02}: {err:#}", day.day));
+                continue;
+            }
+        };
+
+        let statuses = [
+            Status::new(expected.part_1.as_ref(), &report.answers.part_1),
+            Status::new(expected.part_2.as_ref(), &report.answers.part_2),
+        ];
+
+        println!(
+            "{:<5}{:<10}{}",
+            format!("{:02}", day.day),
+            statuses[0].label(),
+            statuses[1].label()
+        );
+
+        for (part, status) in statuses.into_iter().enumerate() {
+            if let Status::Fail { expected, actual } = status {
+                failures.push(format!(
+                    "Day {:02} part {}: expected {expected}, got {actual}",
+                    day.day,
+                    part + 1
+                ));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        for failure in &failures {
+            println!("\n{failure}");
+        }
+
+        bail!("{} checks failed", failures.len());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static ANSWERS: &str = r#"
+        [day-01]
+        part-1 = 24000
+        part-2 = "45000"
+
+        [day-10]
+        part-1 = 13140
+        part-2 = """
+        ##..
+        ..##
+        """
+    "#;
+
+    #[test]
+    fn parse() -> Result<(), Error> {
+        let known = ANSWERS.parse::<KnownAnswers>()?;
+        let day_1 = &known.0[&1];
+        let day_10 = &known.0[&10];
+
+        assert_eq!(day_1.part_1, Some(Answer::Number(24000)));
+        assert_eq!(Status::new(day_1.part_2.as_ref(), "45000"), Status::Pass);
+        assert_eq!(
+            day_10.part_2.as_ref().map(|a| a.to_string()).as_deref(),
+            Some("##..\n..##")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn status() {
+        let expected = Answer::Number(5);
+
+        assert_eq!(Status::new(Some(&expected), "5"), Status::Pass);
+        assert_eq!(Status::new(None, "5"), Status::Missing);
+        assert_eq!(
+            Status::new(Some(&expected), "6"),
+            Status::Fail {
+                expected: "5".into(),
+                actual: "6".into()
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_day() {
+        assert!("[one]\npart-1 = 1".parse::<KnownAnswers>().is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use util::*;
+
+/// Year of the puzzles solved here
+pub const YEAR: u16 = 2022;
+
+const BASE_URL: &str = "https://adventofcode.com";
+
+/// Where the session token is read from, unless `AOC_SESSION` is set
+pub fn session_file() -> Option<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+
+    Some(config.join("aoc").join("session"))
+}
+
+/// Session token from `var`, or else the first line of `file`
+pub fn session(var: Option<String>, file: Option<&Path>) -> Result<String, Error> {
+    let token = match (var, file) {
+        (Some(token), _) => token,
+        (None, Some(file)) if file.is_file() => fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?,
+        _ => bail!("Missing session token: set AOC_SESSION or write it to ~/.config/aoc/session"),
+    };
+    let token = token.trim();
+
+    if token.is_empty() || token.contains(char::is_whitespace) {
+        bail!("Invalid session token");
+    }
+
+    Ok(token.to_string())
+}
+
+/// Logged in client of the Advent of Code website, or a stand-in for it
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    /// Client of `base_url`, defaulting to `AOC_BASE_URL` and then the real site
+    pub fn new(base_url: Option<String>, session: String) -> Self {
+        let base_url = base_url
+            .or_else(|| env::var("AOC_BASE_URL").ok())
+            .unwrap_or_else(|| BASE_URL.into());
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!("aoc-2022/", env!("CARGO_PKG_VERSION"), " (ureq)"))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+        }
+    }
+
+    fn url(&self, day: u8, path: &str) -> String {
+        format!("{}/{YEAR}/day/{day}{path}", self.base_url)
+    }
+
+    /// Send `request`, with `form` as its body unless it's empty
+    fn call(&self, request: ureq::Request, form: &[(&str, &str)]) -> Result<ureq::Response, Error> {
+        let request = request.set("Cookie", &format!("session={}", self.session));
+        let response = match form {
+            [] => request.call(),
+            form => request.send_form(form),
+        };
+
+        match response {
+            Ok(response) => Ok(response),
+            Err(ureq::Error::Status(400, _)) => bail!("Session token rejected"),
+            Err(ureq::Error::Status(404, _)) => bail!("Not available yet"),
+            Err(ureq::Error::Status(status, response)) => {
+                bail!("Server error: {status} {}", response.status_text())
+            }
+            Err(err) => Err(Error::new(err).context("Request failed")),
+        }
+    }
+
+    /// Personal input of a day
+    pub fn input(&self, day: u8) -> Result<String, Error> {
+        let response = self.call(self.agent.get(&self.url(day, "/input")), &[])?;
+        let text = response.into_string().context("Failed to read input")?;
+
+        if text.trim().is_empty() {
+            bail!("Empty input");
+        }
+
+        Ok(text)
+    }
+
+    /// Submit the answer to a part of a day, returning the page in reply
+    pub fn answer(&self, day: u8, part: u8, answer: &str) -> Result<String, Error> {
+        let level = part.to_string();
+        let form = [("level", level.as_str()), ("answer", answer)];
+        let response = self.call(self.agent.post(&self.url(day, "/answer")), &form)?;
+
+        response.into_string().context("Failed to read reply")
+    }
+}
+
+/// Stand-in for the website, answering each request with canned responses
+#[cfg(test)]
+pub mod mock {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    /// Requests received so far, each its request line, headers and body
+    pub type Log = Arc<Mutex<Vec<String>>>;
+
+    /// Start serving on an ephemeral port, returning its base URL
+    pub fn start(respond: fn(&str) -> (u16, String)) -> (String, Log) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+        let base_url = format!("http://{}", listener.local_addr().expect("No address"));
+        let log = Log::default();
+        let requests = Arc::clone(&log);
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(&stream);
+                let mut request = String::new();
+
+                while reader.read_line(&mut request).is_ok_and(|n| n > 2) {}
+
+                let len = request
+                    .lines()
+                    .find_map(|l| {
+                        l.to_lowercase()
+                            .strip_prefix("content-length:")?
+                            .trim()
+                            .parse()
+                            .ok()
+                    })
+                    .unwrap_or(0);
+                let mut body = vec![0; len];
+
+                if reader.read_exact(&mut body).is_err() {
+                    continue;
+                }
+
+                request.push_str(&String::from_utf8_lossy(&body));
+
+                let (status, body) = respond(&request);
+                let response = format!(
+                    "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+
+                requests.lock().expect("Poisoned").push(request);
+                let _ = (&stream).write_all(response.as_bytes());
+            }
+        });
+
+        (base_url, log)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn session() -> Result<(), Error> {
+        let file = env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+
+        fs::write(&file, "abc123\n")?;
+
+        assert_eq!(super::session(Some("env".into()), Some(&file))?, "env");
+        assert_eq!(super::session(None, Some(&file))?, "abc123");
+
+        fs::remove_file(&file)?;
+
+        assert!(super::session(None, Some(&file)).is_err());
+        assert!(super::session(Some(" ".into()), None).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Error> {
+        let (base_url, log) = mock::start(|request| match request.lines().next() {
+            Some("GET /2022/day/1/input HTTP/1.1") => (200, "1\n2\n".into()),
+            Some("GET /2022/day/2/input HTTP/1.1") => (200, "\n".into()),
+            Some("GET /2022/day/3/input HTTP/1.1") => (400, "Log in".into()),
+            _ => (404, "Not found".into()),
+        });
+        let client = Client::new(Some(format!("{base_url}/")), "secret".into());
+
+        assert_eq!(client.input(1)?, "1\n2\n");
+        assert!(log.lock().unwrap()[0].contains("Cookie: session=secret\r\n"));
+
+        let err = |day| client.input(day).unwrap_err().to_string();
+
+        assert_eq!(err(2), "Empty input");
+        assert_eq!(err(3), "Session token rejected");
+        assert_eq!(err(4), "Not available yet");
+
+        Ok(())
+    }
+}
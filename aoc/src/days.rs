@@ -0,0 +1,114 @@
+use util::*;
+
+pub struct Day {
+    pub day: u8,
+    pub solve: fn(&str) -> Result<Report, Error>,
+    pub generate: fn(&mut Rng, usize) -> String,
+    /// Size of a typical personal input
+    pub size: usize,
+}
+
+macro_rules! days {
+    ($($name:ident),* $(,)?) => {
+        &[$(Day {
+            day: <$name::Day as Solution>::DAY,
+            solve: solve::<$name::Day>,
+            generate: <$name::Day as Generate>::generate,
+            size: <$name::Day as Generate>::SIZE,
+        }),*]
+    };
+}
+
+pub static DAYS: &[Day] = days![
+    day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11, day_12,
+    day_13, day_14, day_15, day_16, day_17, day_18, day_19, day_20,
+];
+
+pub fn get(day: u8) -> Result<&'static Day, Error> {
+    DAYS.iter()
+        .find(|d| d.day == day)
+        .with_context(|| format!("Day {day} is not solved"))
+}
+
+fn parse_day(s: &str) -> Result<u8, Error> {
+    s.trim()
+        .parse()
+        .with_context(|| format!("Invalid day: {s}"))
+}
+
+/// Parse a selection of days: `all`, `5`, `1..5`, `1..=20` or a comma
+/// separated list of those
+pub fn select(selection: &str) -> Result<Vec<&'static Day>, Error> {
+    let mut days = Vec::new();
+
+    for part in selection.split(',') {
+        let part = part.trim();
+
+        if part == "all" {
+            days.extend(DAYS);
+            continue;
+        }
+
+        let range = if let Some((l, r)) = part.split_once("..=") {
+            parse_day(l)?..=parse_day(r)?
+        } else if let Some((l, r)) = part.split_once("..") {
+            let r = parse_day(r)?.checked_sub(1).context("Empty range")?;
+
+            parse_day(l)?..=r
+        } else {
+            let day = parse_day(part)?;
+
+            day..=day
+        };
+
+        if range.is_empty() {
+            bail!("Empty range: {part}");
+        }
+
+        for day in range {
+            days.push(get(day)?);
+        }
+    }
+
+    days.sort_by_key(|d| d.day);
+    days.dedup_by_key(|d| d.day);
+
+    Ok(days)
+}
+
+#[cfg(test)]
+mod test {
+    fn select(selection: &str) -> Result<Vec<u8>, super::Error> {
+        Ok(super::select(selection)?.iter().map(|d| d.day).collect())
+    }
+
+    #[test]
+    fn select_ranges() -> Result<(), super::Error> {
+        assert_eq!(select("5")?, [5]);
+        assert_eq!(select("1..4")?, [1, 2, 3]);
+        assert_eq!(select("3..=5,1")?, [1, 3, 4, 5]);
+        assert_eq!(select("all")?.len(), super::DAYS.len());
+
+        Ok(())
+    }
+
+    // Other crates can reuse the days' parsers and solvers directly
+    #[test]
+    fn library() -> Result<(), super::Error> {
+        let elves = day_01::parse("1000\n2000\n\n4000")?;
+        let sensors = day_15::parse("Sensor at x=0, y=0: closest beacon is at x=2, y=0")?;
+
+        assert_eq!(day_01::part_1(&elves)?, 4000);
+        assert_eq!(day_15::gaps(0, 4, &sensors), [3..=4]);
+        assert_eq!(day_15::part_1(0, &sensors)?, 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_invalid() {
+        assert!(select("5..5").is_err());
+        assert!(select("0").is_err());
+        assert!(select("x").is_err());
+    }
+}
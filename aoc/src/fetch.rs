@@ -0,0 +1,122 @@
+use std::{env, fs, path::Path, path::PathBuf};
+
+use util::*;
+
+use crate::{
+    args::Args,
+    client::{self, Client},
+    days,
+};
+
+/// Save a day's input to `path`, in full or not at all
+fn download(client: &Client, path: &Path, day: u8) -> Result<(), Error> {
+    let text = client.input(day)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+
+    // An interrupted download mustn't be mistaken for a cached input
+    let partial = path.with_extension("txt.partial");
+
+    fs::write(&partial, text).with_context(|| format!("Failed to write {}", partial.display()))?;
+    fs::rename(&partial, path).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Download the inputs of `days` missing from `dir`
+///
+/// Inputs never change, so cached ones are never fetched again, and the
+/// client is only made, e.g. looking for a session token, if one's missing.
+fn fetch_all(
+    days: &[&days::Day],
+    dir: &Path,
+    mut connect: impl FnMut() -> Result<Client, Error>,
+) -> Result<(), Error> {
+    let mut client = None;
+    let mut failed = 0;
+
+    for day in days {
+        let path = input::path(dir, day.day);
+
+        if path.is_file() {
+            println!("Day {:02}: cached in {}", day.day, path.display());
+            continue;
+        }
+
+        let client = match &mut client {
+            Some(client) => client,
+            None => client.insert(connect()?),
+        };
+
+        match download(client, &path, day.day) {
+            Ok(()) => println!("Day {:02}: saved to {}", day.day, path.display()),
+            Err(err) => {
+                failed += 1;
+                println!("Day {:02}: Error: {err:#}", day.day);
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{failed} of {} inputs failed to download", days.len());
+    }
+
+    Ok(())
+}
+
+pub fn fetch(mut args: Args) -> Result<(), Error> {
+    let days = days::select(&args.positional("days")?)?;
+    let dir = args
+        .flag("--inputs")?
+        .unwrap_or_else(|| PathBuf::from("inputs"));
+    let base_url = args.flag("--base-url")?;
+
+    args.finish()?;
+
+    fetch_all(&days, &dir, || {
+        let file = client::session_file();
+        let session = client::session(env::var("AOC_SESSION").ok(), file.as_deref())?;
+
+        Ok(Client::new(base_url.clone(), session))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::mock;
+
+    #[test]
+    fn fetch() -> Result<(), Error> {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let (base_url, log) = mock::start(|request| match request.lines().next() {
+            Some("GET /2022/day/1/input HTTP/1.1") => (200, "1\n\n2\n".into()),
+            _ => (404, "Not found".into()),
+        });
+        let connect = || Ok(Client::new(Some(base_url.clone()), "secret".into()));
+
+        fetch_all(&days::select("1")?, &dir, connect)?;
+
+        assert_eq!(fs::read_to_string(input::path(&dir, 1))?, "1\n\n2\n");
+        assert_eq!(log.lock().unwrap().len(), 1);
+
+        // Cached days need neither the server nor a session
+        fetch_all(&days::select("1")?, &dir, || bail!("No session"))?;
+
+        assert_eq!(log.lock().unwrap().len(), 1);
+        assert!(fetch_all(&days::select("1,2")?, &dir, || bail!("No session")).is_err());
+
+        // Other days are still fetched when one fails
+        fs::write(input::path(&dir, 3), "cached")?;
+
+        assert!(fetch_all(&days::select("1..=4")?, &dir, connect).is_err());
+        assert!(!input::path(&dir, 2).exists());
+        assert_eq!(log.lock().unwrap().len(), 3);
+
+        fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+}
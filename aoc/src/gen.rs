@@ -0,0 +1,84 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use util::*;
+
+use crate::{args::Args, days};
+
+/// Write the inputs generated from `count` seeds starting at `seed` to
+/// `<dir>/seed-N.txt`, returning their paths
+fn write_all(
+    day: &days::Day,
+    size: usize,
+    seed: u64,
+    count: u64,
+    dir: &Path,
+) -> Result<Vec<PathBuf>, Error> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    (seed..seed + count)
+        .map(|seed| {
+            let path = dir.join(format!("seed-{seed}.txt"));
+            let text = (day.generate)(&mut Rng::new(seed), size);
+
+            fs::write(&path, text + "\n")
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+
+            Ok(path)
+        })
+        .collect()
+}
+
+pub fn gen(mut args: Args) -> Result<(), Error> {
+    let day = days::get(args.positional("day")?.parse().context("Invalid day")?)?;
+    let size = args.flag("--size")?.unwrap_or(day.size);
+    let seed = args.flag("--seed")?.unwrap_or(0);
+    let count = args.flag("--count")?.unwrap_or(1);
+    let out: Option<PathBuf> = args.flag("--out")?;
+
+    args.finish()?;
+
+    match out {
+        Some(dir) => {
+            for path in write_all(day, size, seed, count, &dir)? {
+                println!("{}", path.display());
+            }
+        }
+        None if count == 1 => println!("{}", (day.generate)(&mut Rng::new(seed), size)),
+        None => bail!("Several inputs need a directory to be written to with --out"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn write_all() -> Result<(), Error> {
+        let dir = std::env::temp_dir().join(format!("aoc-gen-{}", std::process::id()));
+        let day = days::get(1)?;
+        let paths = super::write_all(day, 20, 5, 3, &dir)?;
+        let texts = paths
+            .iter()
+            .map(|path| input::read(path).map(|input| input.text))
+            .collect::<Result<Vec<_>, _>>();
+
+        std::fs::remove_dir_all(&dir)?;
+
+        let texts = texts?;
+
+        assert_eq!(paths.len(), 3);
+        assert!(paths[0].ends_with("seed-5.txt"));
+        assert_eq!(texts[1], (day.generate)(&mut Rng::new(6), 20));
+
+        for text in texts {
+            (day.solve)(&text)?;
+        }
+
+        Ok(())
+    }
+}
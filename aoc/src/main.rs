@@ -0,0 +1,115 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use util::*;
+
+use args::Args;
+
+mod args;
+mod batch;
+mod bench;
+mod check;
+mod client;
+mod days;
+mod fetch;
+mod gen;
+mod serve;
+mod submit;
+
+static USAGE: &str = "Usage:
+    aoc run <days> [--input <file|->] [--inputs <dir>] [--format <text|json>]
+    aoc bench <days> [--inputs <dir>] [--runs <n>] [--baseline <file>]
+        [--threshold <percent>] [--save <file>]
+    aoc check <days> [--inputs <dir>] [--answers <file>]
+    aoc batch <day> <dir> [--jobs <n>]
+    aoc fetch <days> [--inputs <dir>] [--base-url <url>]
+    aoc gen <day> [--size <n>] [--seed <n>] [--count <n>] [--out <dir>]
+    aoc submit <day> <part> [<answer>] [--inputs <dir>] [--history <file>]
+        [--base-url <url>]
+    aoc serve [--addr <host:port>] [--max-size <bytes>] [--timeout <secs>]
+        [--jobs <n>] [--connections <n>]
+
+Days may be `all`, a single day, a range (`1..5`, `1..=20`) or a comma
+separated list of those. Inputs are read from `<dir>/day-NN.txt` and
+default to the `inputs` directory; `run` falls back to the samples in
+`days/day-NN/test` and accepts a single `--input` file (`-` for stdin).";
+
+/// Read the personal input for a day
+pub fn read_input(inputs: &Path, day: u8) -> Result<String, Error> {
+    Ok(input::read(&input::path(inputs, day))?.text)
+}
+
+fn run(mut args: Args) -> Result<(), Error> {
+    let days = days::select(&args.positional("days")?)?;
+    let inputs = args
+        .flag("--inputs")?
+        .unwrap_or_else(|| PathBuf::from("inputs"));
+    let source = args.flag("--input")?.unwrap_or(Source::Discover(inputs));
+    let format: Format = args.flag("--format")?.unwrap_or_default();
+    let mut runs = 0;
+    let mut failed = 0;
+
+    args.finish()?;
+
+    if days.len() > 1 && !matches!(source, Source::Discover(_)) {
+        bail!("A single --input can only be used with a single day");
+    }
+
+    for day in days {
+        let inputs = match source.load(day.day) {
+            Ok(inputs) => inputs,
+            Err(err) => {
+                runs += 1;
+                failed += 1;
+                println!("{}", format.render_error(day.day, &err)?);
+                continue;
+            }
+        };
+
+        for input in inputs {
+            if format == Format::Text {
+                if runs > 0 {
+                    println!();
+                }
+
+                println!("Day {:02} ({})", day.day, input.name);
+            }
+
+            runs += 1;
+
+            match (day.solve)(&input.text) {
+                Ok(report) => println!("{}", format.render(&report)?),
+                Err(err) => {
+                    failed += 1;
+                    println!("{}", format.render_error(day.day, &err)?);
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{failed} of {runs} inputs failed");
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<(), Error> {
+    let mut args = env::args().skip(1);
+    let command = args.next();
+    let args = Args::parse(args);
+
+    match command.as_deref() {
+        Some("run") => run(args),
+        Some("batch") => batch::batch(args),
+        Some("bench") => bench::bench(args),
+        Some("check") => check::check(args),
+        Some("fetch") => fetch::fetch(args),
+        Some("gen") => gen::gen(args),
+        Some("serve") => serve::serve(args),
+        Some("submit") => submit::submit(args),
+        _ => bail!(USAGE),
+    }
+}
@@ -0,0 +1,627 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    panic,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use util::*;
+
+use crate::{args::Args, batch, days};
+
+/// Longest request line and headers accepted
+const MAX_HEAD: u64 = 8 * 1024;
+
+/// How long a client may take to read each write of the response
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long to wait for the rest of a rejected request
+const LINGER: Duration = Duration::from_secs(1);
+
+/// Stack of each solver thread, leaving the recursive days room to spare
+const SOLVER_STACK: usize = 64 << 20;
+
+pub struct Limits {
+    /// Largest input accepted, in bytes
+    pub max_body: usize,
+    /// How long a client may take to send its whole request
+    pub read_timeout: Duration,
+    /// How long to wait for a day to be solved
+    pub timeout: Duration,
+    /// Days being solved at once, including those past their timeout
+    pub jobs: usize,
+    /// Connections open at once, beyond which more are turned away
+    pub connections: usize,
+}
+
+struct Response {
+    status: u16,
+    body: String,
+    allow: Option<&'static str>,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Self {
+            status,
+            body,
+            allow: None,
+        }
+    }
+
+    fn error(status: u16, message: impl fmt::Display) -> Self {
+        let body = serde_json::json!({ "error": message.to_string() });
+
+        Self::json(status, body.to_string())
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            100 => "Continue",
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            411 => "Length Required",
+            413 => "Content Too Large",
+            422 => "Unprocessable Content",
+            431 => "Request Header Fields Too Large",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            _ => "Internal Server Error",
+        }
+    }
+
+    fn write(&self, out: &mut impl Write) -> Result<(), io::Error> {
+        write!(out, "HTTP/1.1 {} {}\r\n", self.status, self.reason())?;
+        write!(out, "Content-Type: application/json\r\n")?;
+        write!(out, "Content-Length: {}\r\n", self.body.len())?;
+
+        if let Some(allow) = self.allow {
+            write!(out, "Allow: {allow}\r\n")?;
+        }
+
+        write!(out, "Connection: close\r\n\r\n{}", self.body)?;
+        out.flush()
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+/// Reads from a connection until `deadline`, however slowly the client
+/// trickles its request in
+struct Deadline {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Read for Deadline {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, io::Error> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+
+        if left.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+struct State {
+    days: &'static [days::Day],
+    limits: Limits,
+    busy: Arc<AtomicUsize>,
+    open: AtomicUsize,
+}
+
+/// Solves puzzle inputs POSTed to `/days/{n}`, answering with the same
+/// records as `--format json`, as a JSON array
+pub struct Server {
+    listener: TcpListener,
+    state: Arc<State>,
+}
+
+impl Server {
+    pub fn bind(
+        addr: impl ToSocketAddrs,
+        days: &'static [days::Day],
+        limits: Limits,
+    ) -> Result<Self, Error> {
+        let listener = TcpListener::bind(addr).context("Failed to bind")?;
+
+        Ok(Self {
+            listener,
+            state: Arc::new(State {
+                days,
+                limits,
+                busy: Arc::default(),
+                open: AtomicUsize::new(0),
+            }),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Handle connections, each on its own thread, forever
+    ///
+    /// Connections over the limit are answered straight away, without
+    /// reading their requests.
+    pub fn run(self) -> Result<(), Error> {
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    eprintln!("Failed to accept: {err}");
+                    continue;
+                }
+            };
+            let connections = self.state.limits.connections;
+
+            if self.state.open.fetch_add(1, Ordering::SeqCst) >= connections {
+                self.state.open.fetch_sub(1, Ordering::SeqCst);
+                reject(
+                    stream,
+                    Response::error(503, format!("All {connections} connections are busy")),
+                );
+                continue;
+            }
+
+            let state = Arc::clone(&self.state);
+
+            thread::spawn(move || {
+                if let Err(err) = state.handle(stream) {
+                    eprintln!("Connection failed: {err:#}");
+                }
+
+                state.open.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Answer a connection without waiting for its request
+fn reject(mut stream: TcpStream, response: Response) {
+    let _ = stream.set_write_timeout(Some(LINGER));
+    let _ = response.write(&mut stream);
+    let _ = stream.shutdown(Shutdown::Write);
+
+    // Whatever of the request already arrived would reset the connection
+    // if left unread
+    let _ = stream.set_nonblocking(true);
+    let _ = io::copy(&mut (&stream).take(MAX_HEAD), &mut io::sink());
+}
+
+impl State {
+    fn handle(&self, stream: TcpStream) -> Result<(), Error> {
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+        let mut reader = BufReader::new(Deadline {
+            stream: stream.try_clone()?,
+            deadline: Instant::now() + self.limits.read_timeout,
+        });
+        let mut stream = stream;
+        let response = match self.read(&mut reader, &mut stream) {
+            Ok(request) => {
+                let response = self.route(request.method.as_str(), &request.path, request.body);
+
+                eprintln!("{} {} {}", request.method, request.path, response.status);
+                response
+            }
+            Err(response) => response,
+        };
+
+        response.write(&mut stream)?;
+
+        // Closing with some of the request unread would reset the connection
+        // before the client got to read the response, so drain it if we can
+        let _ = stream.shutdown(Shutdown::Write);
+        reader.get_mut().deadline = Instant::now() + LINGER;
+        let rest = MAX_HEAD + self.limits.max_body as u64;
+        let _ = io::copy(&mut reader.take(rest), &mut io::sink());
+
+        Ok(())
+    }
+
+    /// Read a request, or the response to reject it with
+    fn read(
+        &self,
+        reader: &mut impl BufRead,
+        stream: &mut impl Write,
+    ) -> Result<Request, Response> {
+        let io_error = |err: io::Error| match err.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+                Response::error(408, "Timed out reading the request")
+            }
+            _ => Response::error(400, err),
+        };
+
+        let mut head = reader.take(MAX_HEAD);
+        let mut lines = Vec::new();
+
+        loop {
+            let mut line = String::new();
+
+            if head.read_line(&mut line).map_err(io_error)? == 0 {
+                return Err(match head.limit() {
+                    0 => Response::error(431, "Headers too large"),
+                    _ => Response::error(400, "Incomplete request"),
+                });
+            }
+
+            let line = line.trim_end_matches(['\r', '\n']).to_string();
+
+            if line.is_empty() {
+                break;
+            }
+
+            lines.push(line);
+        }
+
+        let mut request_line = lines.first().map(|l| l.split(' ')).into_iter().flatten();
+        let (Some(method), Some(path), Some(_version)) = (
+            request_line.next(),
+            request_line.next(),
+            request_line.next(),
+        ) else {
+            return Err(Response::error(400, "Malformed request line"));
+        };
+        let header = |name: &str| {
+            lines[1..].iter().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+
+                key.trim()
+                    .eq_ignore_ascii_case(name)
+                    .then(|| value.trim().to_string())
+            })
+        };
+
+        if header("Transfer-Encoding").is_some() {
+            return Err(Response::error(411, "Chunked bodies aren't supported"));
+        }
+
+        let len = match header("Content-Length") {
+            None if method == "POST" => return Err(Response::error(411, "Missing Content-Length")),
+            None => 0,
+            Some(len) => len
+                .parse::<usize>()
+                .map_err(|_| Response::error(400, format!("Invalid Content-Length: {len}")))?,
+        };
+
+        if len > self.limits.max_body {
+            let max = self.limits.max_body;
+
+            return Err(Response::error(
+                413,
+                format!("Input larger than {max} bytes"),
+            ));
+        }
+
+        // Clients like curl wait for this before sending larger bodies
+        if header("Expect").is_some_and(|e| e.eq_ignore_ascii_case("100-continue")) {
+            stream
+                .write_all(b"HTTP/1.1 100 Continue\r\n\r\n")
+                .map_err(io_error)?;
+        }
+
+        let mut body = vec![0; len];
+
+        reader.read_exact(&mut body).map_err(io_error)?;
+
+        Ok(Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            body: String::from_utf8(body)
+                .map_err(|_| Response::error(400, "Input is not UTF-8"))?,
+        })
+    }
+
+    fn route(&self, method: &str, path: &str, body: String) -> Response {
+        let day = path
+            .strip_prefix("/days/")
+            .and_then(|n| n.parse::<u8>().ok())
+            .and_then(|n| self.days.iter().find(|d| d.day == n));
+
+        match (method, day) {
+            (_, None) => Response::error(404, format!("Not found: {path}")),
+            ("POST", Some(day)) => self.solve(day, input::normalize(&body)),
+            (_, Some(_)) => Response {
+                allow: Some("POST"),
+                ..Response::error(405, format!("Method not allowed: {method}"))
+            },
+        }
+    }
+
+    /// Solve `text` on a thread of its own, giving up on it after the timeout
+    ///
+    /// Threads can't be cancelled, so a day past its timeout keeps its job
+    /// until it's done, and requests are turned away while all are taken.
+    fn solve(&self, day: &'static days::Day, text: String) -> Response {
+        let jobs = self.limits.jobs;
+
+        if self.busy.fetch_add(1, Ordering::SeqCst) >= jobs {
+            self.busy.fetch_sub(1, Ordering::SeqCst);
+
+            return Response::error(503, format!("All {jobs} jobs are busy"));
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let busy = Arc::clone(&self.busy);
+
+        let spawned = thread::Builder::new()
+            .stack_size(SOLVER_STACK)
+            .spawn(move || {
+                let outcome = panic::catch_unwind(|| (day.solve)(&text));
+
+                busy.fetch_sub(1, Ordering::SeqCst);
+                // The request may have timed out and stopped listening
+                let _ = tx.send(outcome);
+            });
+
+        if let Err(err) = spawned {
+            self.busy.fetch_sub(1, Ordering::SeqCst);
+
+            return Response::error(500, format!("Failed to start solving: {err}"));
+        }
+
+        match rx.recv_timeout(self.limits.timeout) {
+            Ok(Ok(Ok(report))) => match serde_json::to_string(&report.records()) {
+                Ok(body) => Response::json(200, body),
+                Err(err) => Response::error(500, format!("{err:#}")),
+            },
+            Ok(Ok(Err(err))) => match Format::Json.render_error(day.day, &err) {
+                Ok(body) => Response::json(422, body),
+                Err(err) => Response::error(500, format!("{err:#}")),
+            },
+            Ok(Err(payload)) => {
+                let message = batch::panic_message(payload.as_ref());
+
+                Response::error(500, format!("Solver panicked: {message}"))
+            }
+            Err(_) => {
+                let secs = self.limits.timeout.as_secs_f64();
+
+                Response::error(504, format!("Not solved within {secs} s"))
+            }
+        }
+    }
+}
+
+pub fn serve(mut args: Args) -> Result<(), Error> {
+    let addr = args
+        .flag::<String>("--addr")?
+        .unwrap_or_else(|| "127.0.0.1:2022".into());
+    let default_jobs = thread::available_parallelism().map_or(1, |n| n.get());
+    let Ok(timeout) = Duration::try_from_secs_f64(args.flag("--timeout")?.unwrap_or(60.0)) else {
+        bail!("Invalid value for --timeout");
+    };
+    let limits = Limits {
+        max_body: args.flag("--max-size")?.unwrap_or(1 << 20),
+        read_timeout: Duration::from_secs(10),
+        timeout,
+        jobs: args.flag("--jobs")?.unwrap_or(default_jobs).max(1),
+        connections: args.flag("--connections")?.unwrap_or(64).max(1),
+    };
+
+    args.finish()?;
+
+    let server = Server::bind(addr, days::DAYS, limits)?;
+
+    eprintln!("Listening on http://{}", server.local_addr()?);
+
+    server.run()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn slow(input: &str) -> Result<Report, Error> {
+        match input {
+            "sleep" => thread::sleep(Duration::from_millis(500)),
+            "panic" => panic!("boom"),
+            _ => {}
+        }
+
+        solve::<day_01::Day>(input)
+    }
+
+    static DAYS: &[days::Day] = &[
+        days::Day {
+            day: 1,
+            solve: slow,
+            generate: <day_01::Day as Generate>::generate,
+            size: 0,
+        },
+        days::Day {
+            day: 7,
+            solve: solve::<day_07::Day>,
+            generate: <day_07::Day as Generate>::generate,
+            size: 0,
+        },
+    ];
+
+    /// Start a server on an ephemeral port
+    fn start(limits: Limits) -> Result<SocketAddr, Error> {
+        let server = Server::bind("127.0.0.1:0", DAYS, limits)?;
+        let addr = server.local_addr()?;
+
+        thread::spawn(|| server.run());
+
+        Ok(addr)
+    }
+
+    fn limits() -> Limits {
+        Limits {
+            max_body: 100,
+            read_timeout: Duration::from_millis(300),
+            timeout: Duration::from_millis(200),
+            jobs: 1,
+            // Connections just answered may not have been counted as closed
+            connections: 8,
+        }
+    }
+
+    /// Send a raw request, returning the status and body of the response
+    fn request(addr: SocketAddr, request: &str) -> Result<(u16, String), Error> {
+        let mut stream = TcpStream::connect(addr)?;
+        let mut response = String::new();
+
+        stream.write_all(request.as_bytes())?;
+        stream.shutdown(Shutdown::Write)?;
+        stream.read_to_string(&mut response)?;
+
+        let (head, body) = response.split_once("\r\n\r\n").context("No body")?;
+        let status = head.split(' ').nth(1).context("No status")?.parse()?;
+
+        Ok((status, body.to_string()))
+    }
+
+    fn post(addr: SocketAddr, path: &str, body: &str) -> Result<(u16, String), Error> {
+        let len = body.len();
+
+        request(
+            addr,
+            &format!("POST {path} HTTP/1.1\r\nHost: test\r\nContent-Length: {len}\r\n\r\n{body}"),
+        )
+    }
+
+    #[test]
+    fn solves() -> Result<(), Error> {
+        let addr = start(limits())?;
+        let (status, body) = post(addr, "/days/1", "1000\r\n2000\n\n3000  \n")?;
+        let records = serde_json::from_str::<serde_json::Value>(&body)?;
+
+        assert_eq!(status, 200, "{body}");
+        assert_eq!(records[0]["part"], 1);
+        assert_eq!(records[0]["answer"], "3000");
+        assert_eq!(records[1]["answer"], "6000");
+        assert!(records[1]["solve_ms"].is_f64());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects() -> Result<(), Error> {
+        let addr = start(limits())?;
+        let get = "GET /days/1 HTTP/1.1\r\n\r\n";
+        let no_length = "POST /days/1 HTTP/1.1\r\n\r\n1";
+        let long = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(10000));
+
+        assert_eq!(post(addr, "/days/2", "1")?.0, 404);
+        assert_eq!(post(addr, "/days/x", "1")?.0, 404);
+        assert_eq!(post(addr, "/", "1")?.0, 404);
+        assert_eq!(request(addr, get)?.0, 405);
+        assert_eq!(request(addr, no_length)?.0, 411);
+        assert_eq!(request(addr, "nonsense\r\n\r\n")?.0, 400);
+        assert_eq!(request(addr, "POST /days/1 HTTP/1.1\r\n")?.0, 400);
+        assert_eq!(request(addr, &long)?.0, 431);
+        assert_eq!(post(addr, "/days/1", &"1\n".repeat(51))?.0, 413);
+
+        let (status, body) = post(addr, "/days/1", "x")?;
+
+        assert_eq!(status, 422);
+        assert!(body.starts_with("{\"day\":1,\"error\":"), "{body}");
+
+        let (status, body) = post(addr, "/days/1", "panic")?;
+
+        assert_eq!((status, body.contains("boom")), (500, true), "{body}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn connections() -> Result<(), Error> {
+        let addr = start(Limits {
+            connections: 2,
+            ..limits()
+        })?;
+        // Both wait for requests that never come
+        let idle = [TcpStream::connect(addr)?, TcpStream::connect(addr)?];
+
+        assert_eq!(request(addr, "")?.0, 503);
+
+        drop(idle);
+        thread::sleep(Duration::from_millis(100));
+
+        assert_eq!(post(addr, "/days/1", "1")?.0, 200);
+
+        Ok(())
+    }
+
+    #[test]
+    fn slow_request() -> Result<(), Error> {
+        let addr = start(limits())?;
+        let mut stream = TcpStream::connect(addr)?;
+        let mut response = String::new();
+
+        // Each header arrives well within the timeout, but not all of them
+        for _ in 0..6 {
+            stream.write_all(b"X-Slow: 1\r\n")?;
+            thread::sleep(Duration::from_millis(100));
+        }
+
+        stream.read_to_string(&mut response)?;
+
+        assert!(response.starts_with("HTTP/1.1 408 "), "{response}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_timeout() {
+        for timeout in ["-1", "NaN", "inf", "1e30"] {
+            let args = Args::parse(["--timeout", timeout].into_iter().map(String::from));
+            let err = serve(args).unwrap_err();
+
+            assert_eq!(err.to_string(), "Invalid value for --timeout", "{timeout}");
+        }
+    }
+
+    // Nested deep enough to overflow the stack if followed all the way
+    #[test]
+    fn deep() -> Result<(), Error> {
+        let addr = start(Limits {
+            max_body: 1 << 21,
+            read_timeout: Duration::from_secs(10),
+            ..limits()
+        })?;
+        let body = format!("$ cd /\n{}", "$ cd a\n".repeat(200_000));
+        let (status, body) = post(addr, "/days/7", &body)?;
+
+        assert_eq!(status, 422, "{body}");
+        assert!(body.contains("nested deeper"), "{body}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn timeout() -> Result<(), Error> {
+        let addr = start(limits())?;
+
+        assert_eq!(post(addr, "/days/1", "sleep")?.0, 504);
+
+        // The timed out solver still holds the only job
+        assert_eq!(post(addr, "/days/1", "1")?.0, 503);
+
+        thread::sleep(Duration::from_millis(500));
+
+        assert_eq!(post(addr, "/days/1", "1")?.0, 200);
+
+        Ok(())
+    }
+}
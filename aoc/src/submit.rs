@@ -0,0 +1,437 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use util::*;
+
+use crate::{
+    args::Args,
+    client::{self, Client},
+    days, read_input,
+};
+
+/// What the website made of an answer
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// Answered again too soon, without the answer being checked
+    RateLimited,
+    /// The part was already solved, or its first part isn't yet
+    WrongLevel,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::RateLimited => "not checked, as answers were sent too recently",
+            Verdict::WrongLevel => "not checked, as that part can't be solved now",
+        })
+    }
+}
+
+/// Seconds in a duration like `1m 20s`, or `one minute` or `5 minutes`
+fn seconds(s: &str) -> Option<u64> {
+    let words = s.split_whitespace().collect::<Vec<_>>();
+    let unit = |unit: &str| match unit.trim_end_matches('s') {
+        "h" | "hour" => Some(3600),
+        "m" | "minute" => Some(60),
+        "" | "second" => Some(1),
+        _ => None,
+    };
+
+    match words[..] {
+        [] => None,
+        ["one" | "a", word] => unit(word),
+        [n, word] if n.parse::<u64>().is_ok() => Some(n.parse::<u64>().ok()? * unit(word)?),
+        _ => words.iter().try_fold(0, |total, word| {
+            let split = word.find(|c: char| !c.is_ascii_digit())?;
+            let (n, word) = word.split_at(split);
+
+            Some(total + n.parse::<u64>().ok()? * unit(word)?)
+        }),
+    }
+}
+
+/// Verdict in the website's reply to an answer, and how long to wait
+/// before answering again, if said
+fn parse_reply(reply: &str) -> Result<(Verdict, Option<u64>), Error> {
+    let text = reply.to_lowercase();
+    // The first duration between the two, as e.g. "you have" comes up more
+    // than once
+    let between = |start: &str, end: &str| {
+        text.match_indices(start).find_map(|(i, _)| {
+            let (between, _) = text[i + start.len()..].split_once(end)?;
+
+            seconds(between)
+        })
+    };
+
+    let verdict = if text.contains("that's the right answer") {
+        Verdict::Correct
+    } else if text.contains("that's not the right answer") {
+        if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        }
+    } else if text.contains("you gave an answer too recently") {
+        Verdict::RateLimited
+    } else if text.contains("you don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        // Replies are whole pages, with the message in their only article
+        let article = reply
+            .split_once("<article>")
+            .map_or(reply, |(_, rest)| rest);
+        let snippet = article.chars().take(200).collect::<String>();
+
+        bail!("Unexpected reply: {snippet}");
+    };
+
+    let wait = between("you have ", " left to wait").or_else(|| between("please wait ", " before"));
+
+    Ok((verdict, wait))
+}
+
+/// Answer sent to the website, and what came of it
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch when it was sent, and until which no
+    /// more answers are accepted
+    pub time: u64,
+    pub wait_until: Option<u64>,
+}
+
+/// Every answer sent so far, one JSON record per line of a file
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let attempts = match fs::read_to_string(path) {
+            Ok(text) => text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .enumerate()
+                .map(|(i, line)| {
+                    serde_json::from_str(line)
+                        .with_context(|| format!("Invalid attempt at {}:{}", path.display(), i + 1))
+                })
+                .collect::<Result<_, Error>>()?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => Err(err).with_context(|| format!("Failed to read {}", path.display()))?,
+        };
+
+        Ok(Self {
+            path: path.into(),
+            attempts,
+        })
+    }
+
+    pub fn push(&mut self, attempt: Attempt) -> Result<(), Error> {
+        let write = || -> Result<(), Error> {
+            if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                fs::create_dir_all(dir)?;
+            }
+
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+
+            writeln!(file, "{}", serde_json::to_string(&attempt)?)?;
+
+            Ok(())
+        };
+
+        write().with_context(|| format!("Failed to write {}", self.path.display()))?;
+        self.attempts.push(attempt);
+
+        Ok(())
+    }
+
+    /// Whether `answer` is worth sending: `Ok(false)` if it's known to be
+    /// correct, or an error if it's known to be wrong or answers must wait
+    pub fn vet(&self, day: u8, part: u8, answer: &str, now: u64) -> Result<bool, Error> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|a| (a.day, a.part) == (day, part));
+        let number = answer.parse::<i128>().ok();
+
+        for attempt in attempts {
+            // How the answer compares to this one, if both are numbers
+            let known = attempt.answer.parse::<i128>().ok();
+            let cmp = number.zip(known).map(|(n, k)| n.cmp(&k));
+
+            match attempt.verdict {
+                Verdict::Correct if attempt.answer == answer => return Ok(false),
+                Verdict::Correct => bail!("The answer is known to be {}", attempt.answer),
+                Verdict::RateLimited | Verdict::WrongLevel => {}
+                _ if attempt.answer == answer => bail!("{answer} was {}", attempt.verdict),
+                Verdict::TooHigh if cmp.is_some_and(Ordering::is_ge) => {
+                    bail!("{answer} is too high, as {} already was", attempt.answer)
+                }
+                Verdict::TooLow if cmp.is_some_and(Ordering::is_le) => {
+                    bail!("{answer} is too low, as {} already was", attempt.answer)
+                }
+                _ => {}
+            }
+        }
+
+        // Waits apply to answers to any puzzle
+        if let Some(until) = self.attempts.iter().filter_map(|a| a.wait_until).max() {
+            if until > now {
+                bail!("Answers must wait another {}s", until - now);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Send `answer` unless the history shows there's no point, recording the
+/// verdict
+fn submit_answer(
+    connect: impl FnOnce() -> Result<Client, Error>,
+    history: &mut History,
+    (day, part): (u8, u8),
+    answer: &str,
+    now: u64,
+) -> Result<Verdict, Error> {
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        bail!("Answers are a single word, not {answer:?}");
+    }
+
+    if !history.vet(day, part, answer, now)? {
+        return Ok(Verdict::Correct);
+    }
+
+    let reply = connect()?.answer(day, part, answer)?;
+    let (verdict, wait) = parse_reply(&reply)?;
+
+    history.push(Attempt {
+        day,
+        part,
+        answer: answer.into(),
+        verdict,
+        time: now,
+        wait_until: wait.map(|wait| now + wait),
+    })?;
+
+    Ok(verdict)
+}
+
+pub fn submit(mut args: Args) -> Result<(), Error> {
+    let day = days::get(args.positional("day")?.parse().context("Invalid day")?)?;
+    let part = match args.positional("part")?.as_str() {
+        "1" => 1,
+        "2" => 2,
+        part => bail!("Invalid part: {part}"),
+    };
+    let answer = args.positional("answer").ok();
+    let inputs = args
+        .flag("--inputs")?
+        .unwrap_or_else(|| PathBuf::from("inputs"));
+    let history = args
+        .flag("--history")?
+        .unwrap_or_else(|| inputs.join("history.jsonl"));
+    let base_url = args.flag("--base-url")?;
+
+    args.finish()?;
+
+    // Without an answer, submit what the day's solver makes of its input
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let report = (day.solve)(&read_input(&inputs, day.day)?)?;
+            let answer = match part {
+                1 => report.answers.part_1,
+                _ => report.answers.part_2,
+            };
+
+            if answer.lines().count() > 1 {
+                bail!("Answer spans several lines, read it and pass it instead:\n{answer}");
+            }
+
+            answer
+        }
+    };
+
+    let mut history = History::load(&history)?;
+    let connect = || {
+        let file = client::session_file();
+        let session = client::session(env::var("AOC_SESSION").ok(), file.as_deref())?;
+
+        Ok(Client::new(base_url, session))
+    };
+    let verdict = submit_answer(connect, &mut history, (day.day, part), &answer, now())?;
+
+    println!("Day {:02} part {part}: {answer} is {verdict}", day.day);
+
+    if verdict != Verdict::Correct {
+        bail!("Answer not accepted");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::mock;
+
+    #[test]
+    fn replies() -> Result<(), Error> {
+        let wrong = "<p>That's not the right answer; your answer is too high.  If you're stuck, \
+            make sure you're using the full input data; please wait one minute before trying \
+            again. [<a href=\"/2022/day/1\">Return to Day 1</a>]</p>";
+        let recent = "<p>You gave an answer too recently; you have to wait after submitting an \
+            answer before trying again.  You have 1m 34s left to wait.</p>";
+
+        assert_eq!(parse_reply(wrong)?, (Verdict::TooHigh, Some(60)));
+        assert_eq!(parse_reply(recent)?, (Verdict::RateLimited, Some(94)));
+        assert_eq!(
+            parse_reply(
+                "That's not the right answer.  Please wait 5 minutes before trying again."
+            )?,
+            (Verdict::Incorrect, Some(300))
+        );
+        assert_eq!(
+            parse_reply("<p>That's the right answer!  You are one gold star closer.</p>")?,
+            (Verdict::Correct, None)
+        );
+        assert_eq!(
+            parse_reply(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )?,
+            (Verdict::WrongLevel, None)
+        );
+        assert!(parse_reply("<html>Log in</html>").is_err());
+
+        assert_eq!(seconds("43s"), Some(43));
+        assert_eq!(seconds("1h 2m 3s"), Some(3723));
+
+        Ok(())
+    }
+
+    fn attempt(part: u8, answer: &str, verdict: Verdict) -> Attempt {
+        Attempt {
+            day: 1,
+            part,
+            answer: answer.into(),
+            verdict,
+            time: 0,
+            wait_until: None,
+        }
+    }
+
+    #[test]
+    fn vet() -> Result<(), Error> {
+        let history = History {
+            path: PathBuf::new(),
+            attempts: vec![
+                attempt(1, "100", Verdict::TooHigh),
+                attempt(1, "10", Verdict::TooLow),
+                attempt(1, "abc", Verdict::Incorrect),
+                attempt(1, "50", Verdict::RateLimited),
+                attempt(2, "7", Verdict::Correct),
+            ],
+        };
+
+        assert!(history.vet(1, 1, "50", 0)?);
+        assert!(history.vet(1, 1, "99", 0)?);
+        assert!(history.vet(1, 1, "xyz", 0)?);
+
+        for wrong in ["100", "101", "10", "-3", "abc"] {
+            assert!(history.vet(1, 1, wrong, 0).is_err(), "{wrong}");
+        }
+
+        assert!(!history.vet(1, 2, "7", 0)?);
+        assert!(history.vet(1, 2, "8", 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn submit() -> Result<(), Error> {
+        let path = env::temp_dir().join(format!("aoc-history-{}.jsonl", std::process::id()));
+        let (base_url, log) = mock::start(|request| {
+            let reply = match request.lines().last() {
+                Some("level=1&answer=100") => {
+                    "That's not the right answer; your answer is too high. Please wait one \
+                    minute before trying again."
+                }
+                Some("level=1&answer=50") => "That's the right answer!",
+                _ => "You don't seem to be solving the right level.",
+            };
+
+            (200, reply.into())
+        });
+        let connect = || Ok(Client::new(Some(base_url.clone()), "secret".into()));
+        let mut history = History::load(&path)?;
+
+        assert_eq!(
+            submit_answer(connect, &mut history, (1, 1), "100", 1000)?,
+            Verdict::TooHigh
+        );
+        assert!(log.lock().unwrap()[0].starts_with("POST /2022/day/1/answer HTTP/1.1\r\n"));
+
+        // Known to be too high, and too soon anyway
+        assert!(submit_answer(connect, &mut history, (1, 1), "150", 1100).is_err());
+        assert!(submit_answer(connect, &mut history, (1, 1), "50", 1030).is_err());
+        assert_eq!(log.lock().unwrap().len(), 1);
+
+        assert_eq!(
+            submit_answer(connect, &mut history, (1, 1), "50", 1060)?,
+            Verdict::Correct
+        );
+        assert_eq!(
+            submit_answer(connect, &mut history, (1, 2), "1", 1100)?,
+            Verdict::WrongLevel
+        );
+        assert_eq!(log.lock().unwrap().len(), 3);
+
+        // The history is kept, so correct answers aren't sent twice
+        let mut history = History::load(&path)?;
+
+        assert_eq!(history.attempts.len(), 3);
+        assert_eq!(history.attempts[0].wait_until, Some(1060));
+        assert_eq!(
+            submit_answer(connect, &mut history, (1, 1), "50", 2000)?,
+            Verdict::Correct
+        );
+        assert_eq!(log.lock().unwrap().len(), 3);
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+}
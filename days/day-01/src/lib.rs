@@ -0,0 +1,141 @@
+//! Day 1: Calorie Counting
+
+use util::*;
+
+pub fn parse(input: &str) -> Result<Vec<u64>, Error> {
+    let mut nums = input
+        .trim()
+        .split("\n\n")
+        .map(|elf| {
+            elf.trim()
+                .split('\n')
+                .map(|line| line.trim().parse::<u64>().at(input, line))
+                .try_fold(0u64, |acc, n| {
+                    acc.checked_add(n?).context("Calories overflow")
+                })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    nums.sort();
+    nums.reverse();
+
+    Ok(nums)
+}
+
+pub fn part_1(input: &[u64]) -> Result<u64, Error> {
+    input.first().copied().context("No elves")
+}
+
+pub fn part_2(input: &[u64]) -> Result<u64, Error> {
+    input
+        .iter()
+        .take(3)
+        .try_fold(0u64, |acc, n| acc.checked_add(*n))
+        .context("Calories overflow")
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 1;
+
+    type Input<'a> = Vec<u64>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 250;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let elves = (0..size.max(1)).map(|_| {
+            let snacks = (0..=rng.below(8)).map(|_| rng.below(59000) + 1000);
+
+            snacks.map(|n| n.to_string()).collect::<Vec<_>>().join("\n")
+        });
+
+        elves.collect::<Vec<_>>().join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        1000
+        2000
+        3000
+
+        4000
+
+        5000
+        6000
+
+        7000
+        8000
+        9000
+
+        10000
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 24000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 45000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["", "1\nx", "18446744073709551615\n1"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let mut text = String::new();
+
+            for _ in 0..=rng.below(10) {
+                for _ in 0..=rng.below(5) {
+                    text += &format!("{}\n", rng.below(100000));
+                }
+
+                text += "\n";
+            }
+
+            let input = super::parse(&text)?;
+            let output = super::Joined(&input, "\n\n").to_string();
+
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(20, 10);
+    }
+}
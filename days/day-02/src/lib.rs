@@ -0,0 +1,204 @@
+//! Day 2: Rock Paper Scissors
+
+use util::*;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u64)]
+pub enum Move {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+#[derive(Clone, Copy)]
+#[repr(u64)]
+enum Outcome {
+    Lose = 0,
+    Draw = 1,
+    Win = 2,
+}
+
+impl Move {
+    fn round(self, opponent: Move) -> u64 {
+        3 * (match (3 + (self as u64) - (opponent as u64)) % 3 {
+            0 => Outcome::Draw,
+            1 => Outcome::Win,
+            _ => Outcome::Lose,
+        }) as u64
+    }
+
+    fn score(self, opponent: Move) -> u64 {
+        (self as u64) + self.round(opponent)
+    }
+}
+
+impl FromStr for Move {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "A" | "X" => Move::Rock,
+            "B" | "Y" => Move::Paper,
+            "C" | "Z" => Move::Scissors,
+            _ => bail!("Unexpected move: {s}"),
+        })
+    }
+}
+
+/// Line of the strategy guide: the opponent's move and the second column
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Round(pub Move, pub Move);
+
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let column = |base, m: Move| char::from(base + m as u8 - 1);
+
+        write!(f, "{} {}", column(b'A', self.0), column(b'X', self.1))
+    }
+}
+
+impl From<Move> for Outcome {
+    fn from(m: Move) -> Self {
+        match m {
+            Move::Rock => Outcome::Lose,
+            Move::Paper => Outcome::Draw,
+            Move::Scissors => Outcome::Win,
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Round>, Error> {
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let (l, r) = line
+                .trim()
+                .split_once(' ')
+                .context("Missing space")
+                .at(input, line)?;
+
+            Ok(Round(
+                Move::from_str(l).at(input, l)?,
+                Move::from_str(r).at(input, r)?,
+            ))
+        })
+        .collect()
+}
+
+pub fn part_1(games: &[Round]) -> Result<u64, Error> {
+    Ok(games.iter().map(|Round(l, r)| r.score(*l)).sum())
+}
+
+pub fn part_2(games: &[Round]) -> Result<u64, Error> {
+    Ok(games
+        .iter()
+        .map(|Round(l, r)| {
+            let r = match (*l as u64 + Outcome::from(*r) as u64) % 3 {
+                0 => Move::Paper,
+                1 => Move::Scissors,
+                _ => Move::Rock,
+            };
+
+            (l, r)
+        })
+        .map(|(l, r)| r.score(*l))
+        .sum())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 2;
+
+    type Input<'a> = Vec<Round>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 2500;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let rounds = (0..size).map(|_| {
+            format!(
+                "{} {}",
+                rng.choose(&['A', 'B', 'C']),
+                rng.choose(&['X', 'Y', 'Z'])
+            )
+        });
+
+        rounds.collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        A Y
+        B X
+        C Z
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let games = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&games)?, 15);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let games = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&games)?, 12);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["A", "A Q"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let text = (0..rng.below(20))
+                .map(|_| {
+                    format!(
+                        "{} {}\n",
+                        rng.choose(&["A", "B", "C"]),
+                        rng.choose(&["X", "Y", "Z"])
+                    )
+                })
+                .collect::<String>();
+            let input = super::parse(&text)?;
+            let output = super::Joined(&input, "\n").to_string();
+
+            super::prop::equal(&output, &text.trim_end().to_string())?;
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(100, 10);
+    }
+}
@@ -0,0 +1,234 @@
+//! Day 3: Rucksack Reorganization
+
+use util::*;
+
+pub type Compartment = [bool; 52];
+
+/// Which items are in each half of a rucksack
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rucksack(pub Compartment, pub Compartment);
+
+fn items(compartment: &Compartment) -> Vec<char> {
+    (b'a'..=b'z')
+        .chain(b'A'..=b'Z')
+        .zip(compartment)
+        .filter(|&(_, &held)| held)
+        .map(|(c, _)| char::from(c))
+        .collect()
+}
+
+impl fmt::Display for Rucksack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (left, right) = (items(&self.0), items(&self.1));
+        let len = left.len().max(right.len());
+
+        // Items repeat so that both halves are the same size
+        for half in [left, right] {
+            for c in half.iter().cycle().take(len) {
+                write!(f, "{c}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_compartment(input: &str, items: &str) -> Result<Compartment, ParseError> {
+    let mut compartment = [false; 52];
+
+    for (i, item) in items.char_indices() {
+        match u8::try_from(item).unwrap_or(0) {
+            c if c.is_ascii_uppercase() => {
+                compartment[usize::from(c - b'A' + 26)] = true;
+            }
+            c if c.is_ascii_lowercase() => {
+                compartment[usize::from(c - b'a')] = true;
+            }
+            _ => {
+                let span = &items[i..(i + item.len_utf8())];
+
+                return Err(ParseError::new(
+                    input,
+                    span,
+                    format!("Invalid item: {item}"),
+                ));
+            }
+        }
+    }
+
+    Ok(compartment)
+}
+
+fn parse_line(input: &str, line: &str) -> Result<Rucksack, ParseError> {
+    let line = line.trim();
+    let mut mid = line.len() / 2;
+
+    // Split on a character boundary so invalid items can be reported
+    while !line.is_char_boundary(mid) {
+        mid += 1;
+    }
+
+    let (left, right) = line.split_at(mid);
+    let left = parse_compartment(input, left)?;
+    let right = parse_compartment(input, right)?;
+
+    Ok(Rucksack(left, right))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Rucksack>, Error> {
+    input
+        .trim()
+        .lines()
+        .map(|line| Ok(parse_line(input, line)?))
+        .collect()
+}
+
+pub fn part_1(rucksacks: &[Rucksack]) -> Result<usize, Error> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .map(|(n, Rucksack(l, r))| {
+            l.iter()
+                .zip(r)
+                .position(|(l, r)| *l && *r)
+                .map(|i| i + 1)
+                .with_context(|| format!("No shared item in rucksack {}", n + 1))
+        })
+        .sum()
+}
+
+pub fn part_2(rucksacks: &[Rucksack]) -> Result<usize, Error> {
+    if !rucksacks.len().is_multiple_of(3) {
+        bail!("Rucksacks don't form groups of three");
+    }
+
+    rucksacks
+        .chunks(3)
+        .enumerate()
+        .map(|(n, group)| {
+            (0..52)
+                .find(|&i| group.iter().all(|Rucksack(l, r)| l[i] || r[i]))
+                .map(|i| i + 1)
+                .with_context(|| format!("No badge in group {}", n + 1))
+        })
+        .sum()
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 3;
+
+    type Input<'a> = Vec<Rucksack>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 300;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+        let mut rucksacks = Vec::new();
+
+        // Every group of three shares a badge, every rucksack a misplaced item
+        for _ in 0..size.div_ceil(3).max(1) {
+            let badge = *rng.choose(&items);
+
+            for _ in 0..3 {
+                let len = rng.below(12) + 2;
+                let mut halves =
+                    [(); 2].map(|_| (0..len).map(|_| *rng.choose(&items)).collect::<Vec<_>>());
+                let shared = *rng.choose(&items);
+
+                halves[0][0] = shared;
+                halves[1][0] = shared;
+                halves[rng.below(2)][1] = badge;
+
+                for half in &mut halves {
+                    rng.shuffle(half);
+                }
+
+                rucksacks.push(halves.concat().into_iter().collect::<String>());
+            }
+        }
+
+        rucksacks.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        vJrwpWtwJgWrhcsFMMfFFhFp
+        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+        PmmdzqPrVvPwwTWBwg
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+        ttgJtRGJQctTZtZT
+        CrZsJsPPZsGzwwsLwLmpwMDw
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 157);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 70);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["ab1c", "ab\ncd", "aa"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+
+        super::prop::check(|rng| {
+            let mut text = String::new();
+
+            for _ in 0..rng.below(20) {
+                for _ in 0..(2 * (rng.below(10) + 1)) {
+                    text.push(*rng.choose(&items));
+                }
+
+                text.push('\n');
+            }
+
+            let input = super::parse(&text)?;
+            let output = super::Joined(&input, "\n").to_string();
+
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(30, 10);
+    }
+}
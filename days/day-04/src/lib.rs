@@ -0,0 +1,184 @@
+//! Day 4: Camp Cleanup
+
+use util::*;
+
+/// Sections assigned to each elf in a pair
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Team(pub IntervalSet, pub IntervalSet);
+
+impl fmt::Display for Team {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Each elf is assigned a single range of sections
+        let ranges = [&self.0, &self.1]
+            .into_iter()
+            .flat_map(IntervalSet::ranges)
+            .map(|r| format!("{}-{}", r.start(), r.end()));
+
+        write!(f, "{}", ranges.collect::<Vec<_>>().join(","))
+    }
+}
+
+fn parse_range(input: &str, s: &str) -> Result<IntervalSet, ParseError> {
+    let s = s.trim();
+    let (l, r) = s.split_once('-').context("Missing end").at(input, s)?;
+    let (l, r) = (l.parse().at(input, l)?, r.parse().at(input, r)?);
+
+    if l > r {
+        return Err(ParseError::new(input, s, "Range ends before it starts"));
+    }
+
+    Ok(IntervalSet::from_iter([l..=r]))
+}
+
+fn parse_line(input: &str, line: &str) -> Result<Team, ParseError> {
+    let line = line.trim();
+    let (l, r) = line
+        .split_once(',')
+        .context("Missing range")
+        .at(input, line)?;
+
+    Ok(Team(parse_range(input, l)?, parse_range(input, r)?))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Team>, Error> {
+    input
+        .trim()
+        .lines()
+        .map(|line| Ok(parse_line(input, line)?))
+        .collect()
+}
+
+pub fn part_1(input: &[Team]) -> Result<usize, Error> {
+    Ok(input
+        .iter()
+        .filter(|Team(l, r)| l.is_superset(r) || r.is_superset(l))
+        .count())
+}
+
+pub fn part_2(input: &[Team]) -> Result<usize, Error> {
+    Ok(input.iter().filter(|Team(l, r)| l.overlaps(r)).count())
+}
+
+/// Sections that both elves of some pair were assigned
+pub fn overlapping(input: &[Team]) -> IntervalSet {
+    input.iter().fold(IntervalSet::new(), |acc, Team(l, r)| {
+        acc.union(&l.intersection(r))
+    })
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 4;
+
+    type Input<'a> = Vec<Team>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 1000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let teams = (0..size).map(|_| {
+            let [a, b, c, d] = [(); 4].map(|_| rng.below(99) + 1);
+
+            format!("{}-{},{}-{}", a.min(b), a.max(b), c.min(d), c.max(d))
+        });
+
+        teams.collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        2-4,6-8
+        2-3,4-5
+        5-7,7-9
+        2-8,3-7
+        6-6,4-6
+        2-6,4-8
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn overlapping() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+        let sections = super::overlapping(&input);
+
+        assert_eq!(sections.ranges().collect::<Vec<_>>(), [3..=7]);
+        assert_eq!(sections.len(), 5);
+        assert!(super::overlapping(&input[..2]).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = super::parse("2-4,6-8\n2-3,4-x\n").unwrap_err();
+        let err = err.downcast_ref::<super::ParseError>();
+
+        assert_eq!(err.map(|e| (e.line, e.columns.clone())), Some((2, 7..8)));
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["1-2", "1-2,3", "a-b,1-2", "3-1,1-2"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let mut text = String::new();
+
+            for _ in 0..rng.below(20) {
+                let [a, b, c, d] = [(); 4].map(|_| rng.below(100));
+
+                text += &format!("{}-{},{}-{}\n", a.min(b), a.max(b), c.min(d), c.max(d));
+            }
+
+            let input = super::parse(&text)?;
+            let output = super::Joined(&input, "\n").to_string();
+
+            super::prop::equal(&output, &text.trim_end().to_string())?;
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(50, 10);
+    }
+}
@@ -0,0 +1,287 @@
+//! Day 5: Supply Stacks
+
+use util::parser::{int, prefixed, Parser};
+use util::*;
+
+pub type Stack = Vec<u8>;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Puzzle {
+    pub stacks: Vec<Stack>,
+    pub moves: Vec<Move>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Move {
+    pub qty: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Move {
+    /// Zero-based indices of the stacks to move between
+    fn indices(&self, stacks: &[Stack]) -> Result<(usize, usize), Error> {
+        let index = |n: usize| {
+            n.checked_sub(1)
+                .filter(|&i| i < stacks.len())
+                .with_context(|| format!("No stack {n}"))
+        };
+
+        Ok((index(self.from)?, index(self.to)?))
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.qty, self.from, self.to)
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+
+        for y in (0..height).rev() {
+            let row = self.stacks.iter().map(|stack| match stack.get(y) {
+                Some(&c) => format!("[{}]", char::from(c)),
+                None => "   ".to_string(),
+            });
+
+            writeln!(f, "{}", row.collect::<Vec<_>>().join(" "))?;
+        }
+
+        let labels = (1..=self.stacks.len()).map(|i| format!(" {i} "));
+
+        writeln!(f, "{}\n", labels.collect::<Vec<_>>().join(" "))?;
+        write!(f, "{}", Joined(&self.moves, "\n"))
+    }
+}
+
+fn parse_stacks(lines: &str) -> Vec<Stack> {
+    let mut lines = lines.trim_matches('\n').lines().rev();
+    let labels = lines.next().unwrap_or_default();
+    let rows = lines.map(str::as_bytes).collect::<Vec<_>>();
+
+    // Every label marks a column of crates, listed from the bottom up
+    labels
+        .bytes()
+        .enumerate()
+        .filter(|&(_, c)| c != b' ')
+        .map(|(x, _)| {
+            rows.iter()
+                .map_while(|row| row.get(x).copied().filter(|&c| c != b' '))
+                .collect()
+        })
+        .collect()
+}
+
+fn parse_moves(input: &str, moves: &str) -> Result<Vec<Move>, ParseError> {
+    let step = (
+        prefixed("move", int()),
+        prefixed("from", int()),
+        prefixed("to", int()),
+    );
+
+    parser::run(
+        input,
+        moves,
+        parser::many_to_end(step.map(|(qty, from, to)| Move { qty, from, to })),
+    )
+}
+
+pub fn parse(input: &str) -> Result<Puzzle, Error> {
+    let (stacks, moves) = input
+        .trim_matches('\n')
+        .split_once("\n\n")
+        .context("Missing moves")
+        .at(input, error::end(input))?;
+
+    Ok(Puzzle {
+        stacks: parse_stacks(stacks),
+        moves: parse_moves(input, moves)?,
+    })
+}
+
+pub fn part_1(puzzle: &Puzzle) -> Result<String, Error> {
+    let mut stacks = puzzle.stacks.clone();
+
+    for mv in &puzzle.moves {
+        let (from, to) = mv.indices(&stacks)?;
+
+        for _ in 0..mv.qty.min(stacks[from].len()) {
+            if let Some(c) = stacks[from].pop() {
+                stacks[to].push(c);
+            }
+        }
+    }
+
+    Ok(stacks
+        .into_iter()
+        .filter_map(|mut stack| stack.pop().map(char::from))
+        .collect())
+}
+
+pub fn part_2(puzzle: &Puzzle) -> Result<String, Error> {
+    let mut stacks = puzzle.stacks.clone();
+
+    for mv in &puzzle.moves {
+        let (from, to) = mv.indices(&stacks)?;
+        let pos = stacks[from].len().saturating_sub(mv.qty);
+        let blocks = stacks[from].split_off(pos);
+
+        stacks[to].extend(blocks);
+    }
+
+    Ok(stacks
+        .into_iter()
+        .filter_map(|mut stack| stack.pop().map(char::from))
+        .collect())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 5;
+
+    type Input<'a> = Puzzle;
+    type Output1 = String;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 500;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let count = rng.below(9) + 1;
+        let mut stacks = (0..count)
+            .map(|_| {
+                (0..rng.below(8))
+                    .map(|_| b'A' + rng.below(26) as u8)
+                    .collect()
+            })
+            .collect::<Vec<Stack>>();
+        let mut moves = Vec::new();
+
+        if stacks.iter().all(Vec::is_empty) {
+            stacks[0].push(b'A');
+        }
+
+        // Only ever move crates that are there
+        while moves.len() < size.max(1) {
+            let from = rng.below(count);
+            let to = rng.below(count);
+
+            if stacks[from].is_empty() {
+                continue;
+            }
+
+            let qty = rng.below(stacks[from].len()) + 1;
+            let pos = stacks[from].len() - qty;
+            let blocks = stacks[from].split_off(pos);
+
+            stacks[to].extend(blocks);
+            moves.push(Move {
+                qty,
+                from: from + 1,
+                to: to + 1,
+            });
+        }
+
+        // Undo the moves so that the listed stacks are where they start
+        for mv in moves.iter().rev() {
+            let pos = stacks[mv.to - 1].len() - mv.qty;
+            let blocks = stacks[mv.to - 1].split_off(pos);
+
+            stacks[mv.from - 1].extend(blocks);
+        }
+
+        Puzzle { stacks, moves }.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+            [D]
+        [N] [C]
+        [Z] [M] [P]
+         1   2   3
+
+        move 1 from 2 to 1
+        move 3 from 1 to 3
+        move 2 from 2 to 1
+        move 1 from 1 to 2
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, "CMZ");
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, "MCD");
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in [
+            "",
+            "[A]\n 1\n\nmove 1 from 0 to 1",
+            "[A]\n 1\n\nmove 1 from 2 to 1",
+            "[A]\n 1\n\nmove x from 1 to 1",
+        ] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let count = rng.below(9) + 1;
+            let stacks = (0..count)
+                .map(|_| {
+                    (0..rng.below(6))
+                        .map(|_| b'A' + rng.below(26) as u8)
+                        .collect()
+                })
+                .collect();
+            let moves = (0..=rng.below(10))
+                .map(|_| super::Move {
+                    qty: rng.below(5) + 1,
+                    from: rng.below(count) + 1,
+                    to: rng.below(count) + 1,
+                })
+                .collect();
+            let input = super::Puzzle { stacks, moves };
+            let output = input.to_string();
+
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(50, 10);
+    }
+}
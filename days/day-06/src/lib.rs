@@ -0,0 +1,99 @@
+//! Day 6: Tuning Trouble
+
+use util::*;
+
+fn find_marker(input: &str, size: usize) -> Result<usize, Error> {
+    for (i, w) in input.trim().as_bytes().windows(size).enumerate() {
+        if w.iter().collect::<HashSet<_>>().len() == size {
+            return Ok(i + size);
+        }
+    }
+
+    bail!("Could not find start sequence")
+}
+
+pub fn part_1(input: &str) -> Result<usize, Error> {
+    find_marker(input, 4)
+}
+
+pub fn part_2(input: &str) -> Result<usize, Error> {
+    find_marker(input, 14)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 6;
+
+    type Input<'a> = &'a str;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        Ok(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 4096;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut letters = ('a'..='z').collect::<Vec<_>>();
+        let mut stream = (0..size.max(14))
+            .map(|_| *rng.choose(&letters[..8]))
+            .collect::<Vec<_>>();
+
+        // A run of fourteen distinct letters, so a fourteen letter marker exists
+        let at = rng.below(stream.len() - 13);
+
+        rng.shuffle(&mut letters);
+        stream.splice(at..(at + 14), letters[..14].iter().copied());
+
+        stream.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        assert_eq!(super::part_1("mjqjpqmgbljsphdztnvjfqwrcgsmlb")?, 7);
+        assert_eq!(super::part_1("bvwbjplbgvbhsrlpgdmjqwftvncz")?, 5);
+        assert_eq!(super::part_1("nppdvjthqldpwncqszvftbrmjlhg")?, 6);
+        assert_eq!(super::part_1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")?, 10);
+        assert_eq!(super::part_1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")?, 11);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        assert_eq!(super::part_2("mjqjpqmgbljsphdztnvjfqwrcgsmlb")?, 19);
+        assert_eq!(super::part_2("bvwbjplbgvbhsrlpgdmjqwftvncz")?, 23);
+        assert_eq!(super::part_2("nppdvjthqldpwncqszvftbrmjlhg")?, 23);
+        assert_eq!(super::part_2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")?, 29);
+        assert_eq!(super::part_2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")?, 26);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["", "aaaa"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(50, 20);
+    }
+}
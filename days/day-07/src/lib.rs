@@ -0,0 +1,382 @@
+//! Day 7: No Space Left On Device
+
+use util::*;
+
+/// Deepest directory accepted, as each level costs the parser stack space
+const MAX_DEPTH: usize = 1000;
+
+enum Cd {
+    Root,
+    Up,
+    None,
+}
+
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Directory<'a> {
+    pub files: HashMap<&'a str, usize>,
+    pub dirs: HashMap<&'a str, Directory<'a>>,
+}
+
+impl Directory<'_> {
+    /// Commands that list this directory and then each one inside it
+    fn write_session(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut dirs = self.dirs.iter().collect::<Vec<_>>();
+        let mut files = self.files.iter().collect::<Vec<_>>();
+
+        dirs.sort_by_key(|&(name, _)| name);
+        files.sort();
+
+        if !dirs.is_empty() || !files.is_empty() {
+            writeln!(f, "$ ls")?;
+        }
+
+        for (name, _) in &dirs {
+            writeln!(f, "dir {name}")?;
+        }
+
+        for (name, size) in files {
+            writeln!(f, "{size} {name}")?;
+        }
+
+        for (name, dir) in dirs {
+            writeln!(f, "$ cd {name}")?;
+            dir.write_session(f)?;
+            writeln!(f, "$ cd ..")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Directory<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "$ cd /")?;
+        self.write_session(f)
+    }
+}
+
+fn parse_cd<'a>(
+    input: &str,
+    name: &'a str,
+    cur: &mut Directory<'a>,
+    commands: &mut impl Iterator<Item = &'a str>,
+    depth: usize,
+) -> Result<Cd, ParseError> {
+    if name == ".." {
+        return Ok(Cd::Up);
+    }
+
+    if name == "/" {
+        return Ok(Cd::Root);
+    }
+
+    if depth >= MAX_DEPTH {
+        return Err(ParseError::new(
+            input,
+            name,
+            format!("Directories nested deeper than {MAX_DEPTH}"),
+        ));
+    }
+
+    let cur = cur.dirs.entry(name).or_default();
+
+    while let Some(cmd) = commands.next() {
+        match parse_command(input, cmd, cur, commands, depth + 1)? {
+            Cd::Up => return Ok(Cd::None),
+            Cd::Root => return Ok(Cd::Root),
+            Cd::None => {}
+        }
+    }
+
+    Ok(Cd::None)
+}
+
+fn parse_ls<'a>(input: &str, lines: &'a str, cur: &mut Directory<'a>) -> Result<(), ParseError> {
+    for line in lines.lines() {
+        let line = line.trim();
+        let (l, r) = line
+            .split_once(' ')
+            .context("Invalid file")
+            .at(input, line)?;
+
+        if l == "dir" {
+            cur.dirs.entry(r.trim()).or_default();
+        } else {
+            cur.files.insert(r.trim(), l.parse().at(input, l)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse a command run `depth` directories below the root
+fn parse_command<'a>(
+    input: &str,
+    cmd: &'a str,
+    cur: &mut Directory<'a>,
+    commands: &mut impl Iterator<Item = &'a str>,
+    depth: usize,
+) -> Result<Cd, ParseError> {
+    let cd = cmd.trim_start_matches("cd ");
+    let ls = cmd.trim_start_matches("ls\n");
+
+    if cd.len() < cmd.len() {
+        return parse_cd(input, cd.trim(), cur, commands, depth);
+    }
+
+    if ls.len() < cmd.len() {
+        parse_ls(input, ls.trim(), cur)?;
+
+        return Ok(Cd::None);
+    }
+
+    let name = cmd.lines().next().unwrap_or(cmd);
+
+    Err(ParseError::new(
+        input,
+        name,
+        format!("Unexpected command: {name}"),
+    ))
+}
+
+pub fn parse(input: &str) -> Result<Directory<'_>, Error> {
+    let mut root = Directory::default();
+    let mut commands = input.trim().split('$').skip(1).map(|c| c.trim());
+
+    while let Some(cmd) = commands.next() {
+        parse_command(input, cmd, &mut root, &mut commands, 0)?;
+    }
+
+    Ok(root)
+}
+
+/// Total size of `d`, adding the size of every directory within it to `sizes`
+fn dir_sizes(d: &Directory, sizes: &mut Vec<usize>) -> Result<usize, Error> {
+    let mut size = d
+        .files
+        .values()
+        .try_fold(0usize, |acc, n| acc.checked_add(*n))
+        .context("Directory too large")?;
+
+    for dir in d.dirs.values() {
+        size = size
+            .checked_add(dir_sizes(dir, sizes)?)
+            .context("Directory too large")?;
+    }
+
+    sizes.push(size);
+
+    Ok(size)
+}
+
+pub fn part_1(root: &Directory) -> Result<usize, Error> {
+    let mut sizes = Vec::new();
+
+    dir_sizes(root, &mut sizes)?;
+
+    Ok(sizes.into_iter().filter(|&size| size <= 100000).sum())
+}
+
+pub fn part_2(root: &Directory) -> Result<usize, Error> {
+    static TOTAL_DISK: usize = 70_000_000;
+    static REQUIRED: usize = 30_000_000;
+
+    let mut sizes = Vec::new();
+    let used = dir_sizes(root, &mut sizes)?;
+    let free = TOTAL_DISK
+        .checked_sub(used)
+        .context("Files don't fit on the disk")?;
+    let needed = REQUIRED.saturating_sub(free);
+
+    sizes
+        .into_iter()
+        .filter(|&size| size >= needed)
+        .min()
+        .context("Could not find a directory to delete")
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 7;
+
+    type Input<'a> = Directory<'a>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+/// Commands exploring a random tree until `files` files have been listed,
+/// leaving the last directories unexplored
+fn session(rng: &mut Rng, files: &mut usize, max_size: usize, depth: usize, out: &mut String) {
+    let here = rng.below(4).min(*files);
+
+    *files -= here;
+
+    let dirs = if depth < 8 && *files > 0 {
+        rng.below(4) + 1
+    } else {
+        0
+    };
+
+    if dirs + here > 0 {
+        *out += "$ ls\n";
+    }
+
+    for i in 0..dirs {
+        *out += &format!("dir d{i}\n");
+    }
+
+    for i in 0..here {
+        *out += &format!("{} f{i}.txt\n", rng.below(max_size) + 1);
+    }
+
+    for i in 0..dirs {
+        if *files > 0 {
+            *out += &format!("$ cd d{i}\n");
+            session(rng, files, max_size, depth + 1, out);
+            *out += "$ cd ..\n";
+        }
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 300;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut out = "$ cd /\n".to_string();
+        let mut files = size;
+        // Everything fits on the disk
+        let max_size = 70_000_000 / size.max(1);
+
+        session(rng, &mut files, max_size, 0, &mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        $ cd /
+        $ ls
+        dir a
+        14848514 b.txt
+        8504156 c.dat
+        dir d
+        $ cd a
+        $ ls
+        dir e
+        29116 f
+        2557 g
+        62596 h.lst
+        $ cd e
+        $ ls
+        584 i
+        $ cd ..
+        $ cd ..
+        $ cd d
+        $ ls
+        4060174 j
+        8033020 d.log
+        5626152 d.ext
+        7214296 k
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 95437);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 24933642);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() {
+        let input = format!("$ cd /\n{}", "$ cd a\n".repeat(1001));
+        let err = super::parse(&input).unwrap_err();
+        let err = err.downcast_ref::<super::ParseError>();
+
+        assert_eq!(err.map(|e| (e.line, e.columns.clone())), Some((1002, 6..7)));
+    }
+
+    #[test]
+    fn malformed() {
+        // Deep enough to overflow the stack if it were followed
+        let deep = format!("$ cd /\n{}", "$ cd a\n$ ls\ndir a\n".repeat(200_000));
+
+        for input in [
+            "$ cd /\n$ ls\nx a",
+            "$ pwd",
+            "$ cd /\n$ ls\n70000001 a",
+            &deep,
+        ] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:.20?}");
+        }
+    }
+
+    /// Explore a random directory tree, sometimes skipping directories
+    fn session(rng: &mut super::rng::Rng, depth: usize, out: &mut String) {
+        let dirs = if depth < 3 { rng.below(3) } else { 0 };
+        let files = rng.below(4);
+
+        if dirs + files > 0 {
+            *out += "$ ls\n";
+        }
+
+        for i in 0..dirs {
+            *out += &format!("dir d{i}\n");
+        }
+
+        for i in 0..files {
+            *out += &format!("{} f{i}.txt\n", rng.below(100000));
+        }
+
+        for i in 0..dirs {
+            if rng.chance(0.8) {
+                *out += &format!("$ cd d{i}\n");
+                session(rng, depth + 1, out);
+                *out += "$ cd ..\n";
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let mut text = "$ cd /\n".to_string();
+
+            session(rng, 0, &mut text);
+
+            let input = super::parse(&text)?;
+            let output = input.to_string();
+
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(50, 20);
+    }
+}
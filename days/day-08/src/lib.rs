@@ -0,0 +1,138 @@
+//! Day 8: Treetop Tree House
+
+use util::*;
+
+pub fn parse(input: &str) -> Result<Grid<u8>, Error> {
+    Ok(Grid::parse(input, |c| {
+        c.to_digit(10).map(|d| d as u8).context("Invalid height")
+    })?)
+}
+
+/// Trees seen from `pos` in each direction, stopping at the first as tall
+fn views(trees: &Grid<u8>, pos: Point2) -> impl Iterator<Item = (usize, bool)> + '_ {
+    let height = trees[pos];
+
+    Direction::ALL.into_iter().map(move |d| {
+        let mut seen = 0;
+
+        for (_, &tree) in trees.ray(pos, d.step()) {
+            seen += 1;
+
+            if tree >= height {
+                return (seen, false);
+            }
+        }
+
+        (seen, true)
+    })
+}
+
+pub fn part_1(trees: &Grid<u8>) -> Result<usize, Error> {
+    if trees.is_empty() {
+        bail!("Empty grid");
+    }
+
+    Ok(trees
+        .positions()
+        .filter(|&pos| views(trees, pos).any(|(_, edge)| edge))
+        .count())
+}
+
+pub fn part_2(trees: &Grid<u8>) -> Result<usize, Error> {
+    trees
+        .positions()
+        .map(|pos| views(trees, pos).map(|(seen, _)| seen).product())
+        .max()
+        .context("Empty grid")
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 8;
+
+    type Input<'a> = Grid<u8>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 99;
+
+    /// Square forest `size` trees wide
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        let rows = (0..size).map(|_| (0..size).map(|_| rng.below(10).to_string()).collect());
+
+        rows.collect::<Vec<String>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        30373
+        25512
+        65332
+        33549
+        35390
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 21);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["", "12\n1", "1a"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let (width, height) = (rng.below(10) + 1, rng.below(10) + 1);
+            let text = (0..height)
+                .map(|_| (0..width).map(|_| rng.below(10).to_string()).collect())
+                .collect::<Vec<String>>()
+                .join("\n");
+            let input = super::parse(&text)?;
+            let output = input.to_string();
+
+            super::prop::equal(&output, &text)?;
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(20, 10);
+    }
+}
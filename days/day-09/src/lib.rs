@@ -0,0 +1,316 @@
+//! Day 9: Rope Bridge
+
+use util::*;
+
+/// Steps the head of the rope takes in one direction
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Motion(pub Direction, pub usize);
+
+impl fmt::Display for Motion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let d = match self.0 {
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+        };
+
+        write!(f, "{d} {}", self.1)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Motion>, Error> {
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            let (l, r) = line
+                .split_once(' ')
+                .context("Missing number")
+                .at(input, line)?;
+            let n = r.parse().at(input, r)?;
+            let d = match l {
+                "L" => Direction::Left,
+                "R" => Direction::Right,
+                "U" => Direction::Up,
+                "D" => Direction::Down,
+                _ => {
+                    return Err(ParseError::new(input, l, format!("Invalid direction: {l}")).into())
+                }
+            };
+
+            Ok(Motion(d, n))
+        })
+        .collect()
+}
+
+/// Move the head of `rope` a step towards `d`, and the other knots after it
+fn step(rope: &mut [Point2], d: Direction) {
+    rope[0] += d.step();
+
+    for i in 1..rope.len() {
+        let head = rope[i - 1];
+        let tail = &mut rope[i];
+
+        // Knots more than a step apart close the gap diagonally
+        if head.chebyshev(*tail) > 1 {
+            *tail += (head - *tail).signum();
+        }
+    }
+}
+
+/// Knots of a rope partway through its motions
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Knots<const N: usize> {
+    pub knots: [Point2; N],
+    /// Motion being made, and how many of its steps were taken
+    pub motion: usize,
+    pub taken: usize,
+    /// Positions the tail has been at
+    pub visited: HashSet<Point2>,
+}
+
+/// Rope of `N` knots following the motions of its head, a step at a time
+pub struct Rope<'a, const N: usize> {
+    motions: &'a [Motion],
+    knots: Knots<N>,
+}
+
+impl<'a, const N: usize> Rope<'a, N> {
+    pub fn new(motions: &'a [Motion]) -> Self {
+        let knots = [Point2::ORIGIN; N];
+
+        Self {
+            motions,
+            knots: Knots {
+                knots,
+                motion: 0,
+                taken: 0,
+                visited: knots.last().copied().into_iter().collect(),
+            },
+        }
+    }
+}
+
+impl<const N: usize> Simulation for Rope<'_, N> {
+    type State = Knots<N>;
+
+    fn step(&mut self) -> Result<bool, Error> {
+        let state = &mut self.knots;
+
+        while let Some(&Motion(d, n)) = self.motions.get(state.motion) {
+            if state.taken < n {
+                step(&mut state.knots, d);
+                state.taken += 1;
+                state.visited.extend(state.knots.last().copied());
+
+                return Ok(true);
+            }
+
+            state.motion += 1;
+            state.taken = 0;
+        }
+
+        Ok(false)
+    }
+
+    fn state(&self) -> &Self::State {
+        &self.knots
+    }
+
+    fn restore(&mut self, snapshot: Self::State) {
+        self.knots = snapshot;
+    }
+}
+
+fn rope_sim<const N: usize>(input: &[Motion]) -> Result<usize, Error> {
+    let mut rope = Rope::<N>::new(input);
+
+    while rope.step()? {}
+
+    Ok(rope.state().visited.len())
+}
+
+pub fn part_1(input: &[Motion]) -> Result<usize, Error> {
+    rope_sim::<2>(input)
+}
+
+pub fn part_2(input: &[Motion]) -> Result<usize, Error> {
+    rope_sim::<10>(input)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 9;
+
+    type Input<'a> = Vec<Motion>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Visualize for Day {
+    /// The ten knot rope of part 2 moving, leaving a trail where its tail went
+    fn visualize(input: &Self::Input<'_>, recorder: &mut dyn viz::Recorder) -> Result<(), Error> {
+        // Knots never stray outside where the head went
+        let mut head = Rope::<1>::new(input);
+
+        while head.step()? {}
+
+        let visited = head.state().visited.iter().copied();
+        let (min, max) = Point2::bounds(visited).context("No motions")?;
+        let size = max - min + Point2::new(1, 1);
+        let mut trail = viz::Image::new(size.x as usize, size.y as usize, viz::Rgb::BLACK);
+        let steps = input.iter().map(|&Motion(_, n)| n).sum::<usize>();
+        // Keep long walks to a thousand frames or so
+        let every = (steps / 1000).max(1);
+        let mut rope = Rope::<10>::new(input);
+        let mut i = 0;
+
+        while rope.step()? {
+            let knots = &rope.state().knots;
+
+            trail.set(knots[9] - min, viz::Rgb(60, 60, 120));
+            i += 1;
+
+            if i % every == 0 || i == steps {
+                let mut frame = trail.clone();
+
+                for (k, &knot) in knots.iter().enumerate().rev() {
+                    let shade = 255 - 16 * k as u8;
+
+                    frame.set(knot - min, viz::Rgb(shade, shade / 2, 0));
+                }
+
+                recorder.record(&frame)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 2000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let motions = (0..size).map(|_| {
+            let d = *rng.choose(&Direction::ALL);
+
+            Motion(d, rng.below(20) + 1).to_string()
+        });
+
+        motions.collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        static INPUT: &str = r#"
+             R 4
+             U 4
+             L 3
+             D 1
+             R 4
+             D 1
+             L 5
+             R 2
+         "#;
+
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 13);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        static INPUT: &str = r#"
+            R 5
+            U 8
+            L 8
+            D 3
+            R 17
+            D 10
+            L 25
+            U 20
+        "#;
+
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 36);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["U", "X 1", "U x"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let text = (0..rng.below(20))
+                .map(|_| format!("{} {}\n", rng.choose(&["L", "R", "U", "D"]), rng.below(20)))
+                .collect::<String>();
+            let input = super::parse(&text)?;
+            let output = super::Joined(&input, "\n").to_string();
+
+            super::prop::equal(&output, &text.trim_end().to_string())?;
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn replay() -> Result<(), super::Error> {
+        let input = super::parse("R 4\nU 0\nU 4\nL 3\nD 1\nR 4")?;
+
+        super::sim::check_replay(&mut super::Rope::<10>::new(&input), 100);
+
+        Ok(())
+    }
+
+    #[test]
+    fn visualize() -> Result<(), super::Error> {
+        use super::viz::{Frame, Image, Rgb};
+
+        let input = super::parse("R 4\nU 4\nL 3")?;
+        let mut frames = Vec::<Image>::new();
+
+        <super::Day as super::Visualize>::visualize(&input, &mut frames)?;
+
+        assert_eq!(frames.len(), 11);
+
+        // The head ends up in the top row, the bottom being where it started
+        let last = &frames[10];
+
+        assert_eq!((last.width(), last.height()), (5, 5));
+        assert_eq!(last.pixel(1, 0), Rgb(255, 127, 0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(100, 10);
+    }
+}
@@ -0,0 +1,257 @@
+//! Day 10: Cathode-Ray Tube
+
+use util::*;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Op {
+    Noop,
+    AddrX(i64),
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Op::Noop => write!(f, "noop"),
+            Op::AddrX(n) => write!(f, "addx {n}"),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Op>, Error> {
+    input
+        .trim()
+        .lines()
+        .map(|l| {
+            Ok(match l.trim() {
+                "noop" => Op::Noop,
+                op => {
+                    let n = op.trim_start_matches("addx ");
+
+                    Op::AddrX(n.parse().at(input, n)?)
+                }
+            })
+        })
+        .collect()
+}
+
+/// Registers of the CPU between cycles
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Registers {
+    /// Cycles completed so far
+    pub cycle: usize,
+    pub x: i64,
+    /// Next instruction, and whether its first cycle is done
+    pub pc: usize,
+    pub busy: bool,
+}
+
+/// CPU running a program, a cycle at a time
+pub struct Cpu<'a> {
+    ops: &'a [Op],
+    registers: Registers,
+}
+
+impl<'a> Cpu<'a> {
+    pub fn new(ops: &'a [Op]) -> Self {
+        Self {
+            ops,
+            registers: Registers {
+                x: 1,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl Simulation for Cpu<'_> {
+    type State = Registers;
+
+    fn step(&mut self) -> Result<bool, Error> {
+        let r = &mut self.registers;
+        let Some(op) = self.ops.get(r.pc) else {
+            return Ok(false);
+        };
+
+        match op {
+            Op::AddrX(_) if !r.busy => r.busy = true,
+            Op::AddrX(n) => {
+                r.x = r.x.checked_add(*n).context("Register overflow")?;
+                r.busy = false;
+                r.pc += 1;
+            }
+            Op::Noop => r.pc += 1,
+        }
+
+        r.cycle += 1;
+
+        Ok(true)
+    }
+
+    fn state(&self) -> &Self::State {
+        &self.registers
+    }
+
+    fn restore(&mut self, snapshot: Self::State) {
+        self.registers = snapshot;
+    }
+}
+
+pub fn part_1(ops: &[Op]) -> Result<i64, Error> {
+    let mut sum = 0i64;
+    let mut cpu = Cpu::new(ops);
+
+    loop {
+        // The register during a cycle is what it was before it
+        let Registers { cycle, x, .. } = *cpu.state();
+        let i = cycle + 1;
+
+        if !cpu.step()? {
+            break;
+        }
+
+        match i {
+            20 | 60 | 100 | 140 | 180 | 220 => {
+                sum = (i as i64)
+                    .checked_mul(x)
+                    .and_then(|strength| sum.checked_add(strength))
+                    .context("Signal strength overflow")?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(sum)
+}
+
+pub fn part_2(ops: &[Op]) -> Result<String, Error> {
+    let cycles = ops
+        .iter()
+        .map(|op| match op {
+            Op::Noop => 1,
+            Op::AddrX(_) => 2,
+        })
+        .sum::<usize>();
+
+    let mut screen = Grid::new(40, cycles.div_ceil(40), '.');
+    let mut cpu = Cpu::new(ops);
+
+    loop {
+        let Registers { cycle: i, x, .. } = *cpu.state();
+        let pos = Point2::new((i % 40) as isize, (i / 40) as isize);
+
+        if !cpu.step()? {
+            break;
+        }
+
+        if x.abs_diff(pos.x as i64) <= 1 {
+            screen[pos] = '#';
+        }
+    }
+
+    Ok(screen.to_string())
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 10;
+
+    type Input<'a> = Vec<Op>;
+    type Output1 = i64;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 140;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        // Keeps the sprite around the screen
+        let mut x = 1;
+        let ops = (0..size).map(|_| {
+            if rng.chance(0.3) {
+                return Op::Noop.to_string();
+            }
+
+            let n = rng.range(-10..=10).clamp(-5 - x, 44 - x);
+
+            x += n;
+            Op::AddrX(n as i64).to_string()
+        });
+
+        ops.collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = include_str!("../test/sample.txt");
+    static OUTPUT: &str = include_str!("../test/output.txt");
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 13140);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, OUTPUT.trim());
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["addx", "addx 9223372036854775807\naddx 1"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn replay() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        super::sim::check_replay(&mut super::Cpu::new(&input), 30);
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let text = (0..rng.below(50))
+                .map(|_| match rng.below(2) {
+                    0 => "noop\n".to_string(),
+                    _ => format!("addx {}\n", rng.range(-20..=20)),
+                })
+                .collect::<String>();
+            let input = super::parse(&text)?;
+            let output = super::Joined(&input, "\n").to_string();
+
+            super::prop::equal(&output, &text.trim_end().to_string())?;
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(140, 10);
+    }
+}
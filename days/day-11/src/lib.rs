@@ -0,0 +1,368 @@
+//! Day 11: Monkey in the Middle
+
+use util::parser::{int, list, prefixed, tag, Parser};
+use util::*;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Operation {
+    Add(usize),
+    Mul(usize),
+    Square,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Monkey {
+    pub id: usize,
+    pub items: VecDeque<usize>,
+    pub operation: Operation,
+    pub test_div: usize,
+    pub on_true: usize,
+    pub on_false: usize,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operation::Add(n) => write!(f, "old + {n}"),
+            Operation::Mul(n) => write!(f, "old * {n}"),
+            Operation::Square => write!(f, "old * old"),
+        }
+    }
+}
+
+impl fmt::Display for Monkey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let items = self.items.iter().map(usize::to_string);
+
+        writeln!(f, "Monkey {}:", self.id)?;
+        writeln!(
+            f,
+            "  Starting items: {}",
+            items.collect::<Vec<_>>().join(", ")
+        )?;
+        writeln!(f, "  Operation: new = {}", self.operation)?;
+        writeln!(f, "  Test: divisible by {}", self.test_div)?;
+        writeln!(f, "    If true: throw to monkey {}", self.on_true)?;
+        write!(f, "    If false: throw to monkey {}", self.on_false)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Monkey>, Error> {
+    let operation = prefixed(
+        "*",
+        tag("old")
+            .map(|_| Operation::Square)
+            .or(int().map(Operation::Mul)),
+    )
+    .or(prefixed("+", int().map(Operation::Add)))
+    .label("operation");
+
+    let monkey = (
+        prefixed("Monkey", int::<usize>()).skip(tag(":")),
+        prefixed("Starting items:", list(int(), ",")),
+        prefixed("Operation: new = old", operation),
+        prefixed("Test: divisible by", int()),
+        prefixed("If true: throw to monkey", int()),
+        prefixed("If false: throw to monkey", int()),
+    )
+        .map(
+            |(id, items, operation, test_div, on_true, on_false)| Monkey {
+                id,
+                items: VecDeque::from(items),
+                operation,
+                test_div,
+                on_true,
+                on_false,
+            },
+        );
+
+    Ok(parser::run(input, input, parser::many_to_end(monkey))?)
+}
+
+fn validate(input: &[Monkey]) -> Result<(), Error> {
+    for (i, monkey) in input.iter().enumerate() {
+        if monkey.test_div == 0 {
+            bail!("Monkey {i} tests divisibility by zero");
+        }
+
+        // Throwing to itself would never empty the monkey's hands
+        for target in [monkey.on_true, monkey.on_false] {
+            if target == i || target >= input.len() {
+                bail!("Monkey {i} can't throw to monkey {target}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Monkeys and what they hold after some rounds
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Troop {
+    pub monkeys: Vec<Monkey>,
+    /// Items each monkey has inspected
+    pub totals: Vec<usize>,
+    pub round: usize,
+}
+
+/// Monkeys playing keep away, a round at a time
+pub struct KeepAway {
+    worry_fac: usize,
+    /// Product of the divisors, under which worry levels are kept
+    f: usize,
+    troop: Troop,
+}
+
+impl KeepAway {
+    /// Game where worry levels are divided by `worry_fac` after inspection
+    pub fn new(input: &[Monkey], worry_fac: usize) -> Result<Self, Error> {
+        validate(input)?;
+
+        if worry_fac == 0 {
+            bail!("Worry levels divided by zero");
+        }
+
+        let f = input
+            .iter()
+            .try_fold(1usize, |acc, m| acc.checked_mul(m.test_div))
+            .context("Divisors overflow")?;
+
+        Ok(Self {
+            worry_fac,
+            f,
+            troop: Troop {
+                monkeys: input.to_vec(),
+                totals: vec![0; input.len()],
+                round: 0,
+            },
+        })
+    }
+}
+
+impl Simulation for KeepAway {
+    type State = Troop;
+
+    /// Play a round, which never ends the game
+    fn step(&mut self) -> Result<bool, Error> {
+        let Troop {
+            monkeys, totals, ..
+        } = &mut self.troop;
+
+        for i in 0..monkeys.len() {
+            while let Some(worry) = monkeys[i].items.pop_front() {
+                totals[i] += 1;
+
+                let worry = match monkeys[i].operation {
+                    Operation::Add(n) => worry.checked_add(n),
+                    Operation::Mul(n) => worry.checked_mul(n),
+                    Operation::Square => worry.checked_mul(worry),
+                }
+                .context("Worry level overflow")?;
+                let worry = (worry / self.worry_fac) % self.f;
+
+                let target = if worry.is_multiple_of(monkeys[i].test_div) {
+                    monkeys[i].on_true
+                } else {
+                    monkeys[i].on_false
+                };
+
+                monkeys[target].items.push_back(worry);
+            }
+        }
+
+        self.troop.round += 1;
+
+        Ok(true)
+    }
+
+    fn state(&self) -> &Self::State {
+        &self.troop
+    }
+
+    fn restore(&mut self, snapshot: Self::State) {
+        self.troop = snapshot;
+    }
+}
+
+fn puzzle(input: &[Monkey], rounds: usize, worry_fac: usize) -> Result<usize, Error> {
+    let mut game = KeepAway::new(input, worry_fac)?;
+
+    game.run_until(|troop| troop.round == rounds)?;
+
+    let mut totals = game.troop.totals;
+
+    totals.sort();
+
+    match (totals.pop(), totals.pop()) {
+        (Some(a), Some(b)) => a.checked_mul(b).context("Monkey business overflow"),
+        _ => bail!("Need at least two monkeys"),
+    }
+}
+
+pub fn part_1(input: &[Monkey]) -> Result<usize, Error> {
+    puzzle(input, 20, 3)
+}
+
+pub fn part_2(input: &[Monkey]) -> Result<usize, Error> {
+    puzzle(input, 10000, 1)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 11;
+
+    type Input<'a> = Vec<Monkey>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 8;
+
+    /// Between two and eight monkeys, testing distinct primes so that worry
+    /// levels stay small
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let count = size.clamp(2, 8);
+        let mut primes = [2, 3, 5, 7, 11, 13, 17, 19];
+
+        rng.shuffle(&mut primes);
+
+        let monkeys = (0..count)
+            .map(|id| {
+                // Never throws to itself
+                let [on_true, on_false] = [(); 2].map(|_| (id + 1 + rng.below(count - 1)) % count);
+
+                Monkey {
+                    id,
+                    items: (0..=rng.below(7)).map(|_| rng.below(50) + 50).collect(),
+                    operation: match rng.below(5) {
+                        0 => Operation::Square,
+                        1 | 2 => Operation::Add(rng.below(8) + 1),
+                        _ => Operation::Mul(rng.below(18) + 2),
+                    },
+                    test_div: primes[id],
+                    on_true,
+                    on_false,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Joined(&monkeys, "\n\n").to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        Monkey 0:
+            Starting items: 79, 98
+            Operation: new = old * 19
+            Test: divisible by 23
+                If true: throw to monkey 2
+                If false: throw to monkey 3
+
+        Monkey 1:
+            Starting items: 54, 65, 75, 74
+            Operation: new = old + 6
+            Test: divisible by 19
+                If true: throw to monkey 2
+                If false: throw to monkey 0
+
+        Monkey 2:
+            Starting items: 79, 60, 97
+            Operation: new = old * old
+            Test: divisible by 13
+                If true: throw to monkey 1
+                If false: throw to monkey 3
+
+        Monkey 3:
+            Starting items: 74
+            Operation: new = old + 3
+            Test: divisible by 17
+                If true: throw to monkey 0
+                If false: throw to monkey 1
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 10605);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 2713310158);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in [
+            String::new(),
+            INPUT.replace("divisible by 23", "divisible by 0"),
+            INPUT.replacen("throw to monkey 2", "throw to monkey 0", 1),
+            INPUT.replace("throw to monkey 3", "throw to monkey 4"),
+            INPUT.replace("old * 19", "old * 18446744073709551615"),
+            INPUT.replace("old * 19", "old / 19"),
+        ] {
+            assert!(super::solve::<super::Day>(&input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn replay() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        super::sim::check_replay(&mut super::KeepAway::new(&input, 1)?, 20);
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let count = rng.below(8) + 2;
+            let monkeys = (0..count)
+                .map(|id| super::Monkey {
+                    id,
+                    items: (0..=rng.below(5)).map(|_| rng.below(100)).collect(),
+                    operation: match rng.below(3) {
+                        0 => super::Operation::Add(rng.below(10)),
+                        1 => super::Operation::Mul(rng.below(20)),
+                        _ => super::Operation::Square,
+                    },
+                    test_div: rng.below(20) + 1,
+                    on_true: rng.below(count),
+                    on_false: rng.below(count),
+                })
+                .collect::<Vec<_>>();
+            let output = super::Joined(&monkeys, "\n\n").to_string();
+
+            super::prop::equal(&super::parse(&output)?, &monkeys)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(8, 10);
+    }
+}
@@ -0,0 +1,295 @@
+//! Day 12: Hill Climbing Algorithm
+
+use util::*;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Puzzle {
+    pub start: Point2,
+    pub end: Point2,
+    pub map: Grid<u8>,
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut map = self.map.clone();
+
+        map[self.start] = b'S';
+        map[self.end] = b'E';
+
+        let (min, max) = map.bounds();
+
+        for y in min.y..=max.y {
+            if y > min.y {
+                writeln!(f)?;
+            }
+
+            let row = map.row(y).unwrap_or_default();
+
+            write!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Puzzle {
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut map = Grid::parse(input, |c| match c {
+            'a'..='z' | 'S' | 'E' => Ok(c as u8),
+            _ => Err("Invalid height"),
+        })?;
+
+        for (m, name) in [("S", "start"), ("E", "end")] {
+            if let Some((i, _)) = input.match_indices(m).nth(1) {
+                let span = &input[i..(i + m.len())];
+
+                return Err(ParseError::new(input, span, format!("Duplicate {name}")).into());
+            }
+        }
+
+        let find = |m| map.iter().find(|&(_, &c)| c == m).map(|(pos, _)| pos);
+
+        let start = find(b'S')
+            .context("Missing start")
+            .at(input, error::end(input))?;
+        let end = find(b'E')
+            .context("Missing end")
+            .at(input, error::end(input))?;
+
+        map[start] = b'a';
+        map[end] = b'z';
+
+        Ok(Self { start, end, map })
+    }
+
+    /// Positions one step from `pos`, walking backwards from the end if `reverse`
+    fn steps(&self, pos: Point2, reverse: bool) -> impl Iterator<Item = Point2> + '_ {
+        let c = self.map[pos];
+
+        self.map.neighbors4(pos).filter(move |&p| {
+            let (from, to) = if reverse {
+                (self.map[p], c)
+            } else {
+                (c, self.map[p])
+            };
+
+            to <= from || to - from == 1
+        })
+    }
+
+    pub fn part_1(&self) -> Result<usize, Error> {
+        let end = self.end;
+        let paths = graph::astar(
+            self.start,
+            &end,
+            |&pos| self.steps(pos, false).map(|p| (p, 1)),
+            |pos| pos.manhattan(end),
+        );
+
+        paths.dist.get(&end).copied().context("No solution")
+    }
+
+    pub fn part_2(&self) -> Result<usize, Error> {
+        let costs = graph::bfs(self.end, |&pos| self.steps(pos, true)).dist;
+
+        self.map
+            .iter()
+            .filter(|&(_, &c)| c == b'a')
+            .flat_map(|(pos, _)| costs.get(&pos).copied())
+            .min()
+            .context("No solution")
+    }
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 12;
+
+    type Input<'a> = Puzzle;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        Puzzle::parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        input.part_1()
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        input.part_2()
+    }
+}
+
+impl Visualize for Day {
+    /// The search of part 1 spreading out from the start, one step at a
+    /// time, and then the shortest path it found
+    fn visualize(input: &Self::Input<'_>, recorder: &mut dyn viz::Recorder) -> Result<(), Error> {
+        let shade = |h: u8, (r, g, b): (u16, u16, u16)| {
+            let h = (h - b'a') as u16 + 5;
+
+            viz::Rgb((r * h / 30) as u8, (g * h / 30) as u8, (b * h / 30) as u8)
+        };
+        let mut image = viz::Image::from_grid(&input.map, |&h| shade(h, (60, 200, 60)));
+        let mut parents = HashMap::from([(input.start, input.start)]);
+        let mut frontier = vec![input.start];
+
+        while !frontier.is_empty() && !parents.contains_key(&input.end) {
+            let mut next = Vec::new();
+
+            for &pos in &frontier {
+                image.set(pos, shade(input.map[pos], (80, 120, 255)));
+
+                for p in input.steps(pos, false) {
+                    if let Entry::Vacant(e) = parents.entry(p) {
+                        e.insert(pos);
+                        next.push(p);
+                    }
+                }
+            }
+
+            recorder.record(&image)?;
+            frontier = next;
+        }
+
+        let mut pos = input.end;
+
+        while let Some(&parent) = parents.get(&pos).filter(|_| pos != input.start) {
+            image.set(pos, viz::Rgb(255, 80, 40));
+            pos = parent;
+        }
+
+        image.set(input.start, viz::Rgb::WHITE);
+        image.set(input.end, viz::Rgb::WHITE);
+
+        recorder.record(&image)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 160;
+
+    /// Hillside `size` squares wide, rising from west to east, with one row
+    /// that climbs without any steep steps
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let width = size.max(26);
+        let height = width / 4 + 1;
+        let path = rng.below(height);
+        let rows = (0..height).map(|y| {
+            (0..width)
+                .map(|x| {
+                    let base = x * 25 / (width - 1);
+                    let dip = if y == path { 0 } else { rng.below(3) };
+
+                    match x {
+                        0 if y == path => 'S',
+                        _ if x == width - 1 && y == path => 'E',
+                        _ => char::from(b'a' + base.saturating_sub(dip) as u8),
+                    }
+                })
+                .collect::<String>()
+        });
+
+        rows.collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        Sabqponm
+        abcryxxl
+        accszExk
+        acctuvwj
+        abdefghi
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let puzzle = super::Puzzle::parse(INPUT)?;
+
+        assert_eq!(puzzle.part_1()?, 31);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let puzzle = super::Puzzle::parse(INPUT)?;
+
+        assert_eq!(puzzle.part_2()?, 29);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = super::Puzzle::parse("SbE\nSaa\n").unwrap_err();
+        let err = err.downcast_ref::<super::ParseError>();
+
+        assert_eq!(err.map(|e| (e.line, e.columns.clone())), Some((2, 1..2)));
+        assert_eq!(err.map(|e| e.message.as_str()), Some("Duplicate start"));
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["", "abc", "S#E", "SbE", "SbE\nSaa", "SEE"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn visualize() -> Result<(), super::Error> {
+        use super::viz::{Frame, Image, Rgb};
+
+        let puzzle = super::Puzzle::parse(INPUT)?;
+        let mut frames = Vec::<Image>::new();
+
+        <super::Day as super::Visualize>::visualize(&puzzle, &mut frames)?;
+
+        // A frame per step of the search, and one with the path
+        assert_eq!(frames.len(), 32);
+
+        let last = &frames[31];
+        let path = (0..last.height())
+            .flat_map(|y| (0..last.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| last.pixel(x, y) == Rgb(255, 80, 40));
+
+        assert_eq!(path.count(), 30);
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let (width, height) = (rng.below(10) + 2, rng.below(10) + 1);
+            let mut cells = (0..(width * height))
+                .map(|_| b'a' + rng.below(26) as u8)
+                .collect::<Vec<_>>();
+            let start = rng.below(cells.len());
+            let end = (start + 1 + rng.below(cells.len() - 1)) % cells.len();
+
+            cells[start] = b'S';
+            cells[end] = b'E';
+
+            let text = cells
+                .chunks(width)
+                .map(|row| String::from_utf8_lossy(row))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let input = super::Puzzle::parse(&text)?;
+            let output = input.to_string();
+
+            super::prop::equal(&output, &text)?;
+            super::prop::equal(&super::Puzzle::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(30, 10);
+    }
+}
@@ -0,0 +1,222 @@
+//! Day 13: Distress Signal
+
+use serde::Deserialize;
+use std::slice;
+use util::*;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum Item {
+    List(Vec<Item>),
+    Value(usize),
+}
+
+impl Item {
+    fn as_slice(&self) -> &[Item] {
+        match self {
+            Item::List(v) => v,
+            Item::Value(_) => slice::from_ref(self),
+        }
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Item::List(items) => write!(f, "[{}]", Joined(items, ",")),
+            Item::Value(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Item>, Error> {
+    input
+        .trim()
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            serde_json::from_str(l).map_err(|err| {
+                // serde_json reports the column of the offending character
+                let i = err.column().saturating_sub(1).min(l.len());
+                let span = l.get(i..(i + 1)).unwrap_or(&l[l.len()..]);
+
+                ParseError::new(input, span, err).into()
+            })
+        })
+        .collect()
+}
+
+impl Ord for Item {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Item::Value(l), Item::Value(r)) => l.cmp(r),
+            _ => self.as_slice().cmp(other.as_slice()),
+        }
+    }
+}
+
+impl PartialOrd for Item {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub fn part_1(input: &[Item]) -> Result<usize, Error> {
+    if !input.len().is_multiple_of(2) {
+        bail!("Unpaired packet");
+    }
+
+    Ok(input
+        .chunks_exact(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0] < pair[1])
+        .map(|(i, _)| i + 1)
+        .sum())
+}
+
+pub fn part_2(input: &[Item]) -> Result<usize, Error> {
+    let mut input = input.to_vec();
+    let a = Item::List(vec![Item::List(vec![Item::Value(2)])]);
+    let b = Item::List(vec![Item::List(vec![Item::Value(6)])]);
+
+    input.push(a.clone());
+    input.push(b.clone());
+    input.sort();
+
+    let a = input
+        .iter()
+        .position(|i| i == &a)
+        .context("Missing divider")?;
+    let b = input
+        .iter()
+        .position(|i| i == &b)
+        .context("Missing divider")?;
+
+    Ok((a + 1) * (b + 1))
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 13;
+
+    type Input<'a> = Vec<Item>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+/// Random packet, nested at most a few lists deep
+fn packet(rng: &mut Rng, depth: usize) -> Item {
+    if depth > 0 && (depth > 3 || rng.chance(0.5)) {
+        return Item::Value(rng.below(11));
+    }
+
+    let items = (0..rng.below(5)).map(|_| packet(rng, depth + 1));
+
+    Item::List(items.collect())
+}
+
+impl Generate for Day {
+    const SIZE: usize = 150;
+
+    /// `size` pairs of packets
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let pairs = (0..size).map(|_| format!("{}\n{}", packet(rng, 0), packet(rng, 0)));
+
+        pairs.collect::<Vec<_>>().join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        [1,1,3,1,1]
+        [1,1,5,1,1]
+
+        [[1],[2,3,4]]
+        [[1],4]
+
+        [9]
+        [[8,7,6]]
+
+        [[4,4],4,4]
+        [[4,4],4,4,4]
+
+        [7,7,7,7]
+        [7,7,7]
+
+        []
+        [3]
+
+        [[[]]]
+        [[]]
+
+        [1,[2,[3,[4,[5,6,7]]]],8,9]
+        [1,[2,[3,[4,[5,6,0]]]],8,9]
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 13);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 140);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["[1]\n[2", "[1]\n[2]\n[3]"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    fn packet(rng: &mut super::rng::Rng, depth: usize) -> super::Item {
+        if depth > 0 && rng.chance(0.5) {
+            return super::Item::Value(rng.below(11));
+        }
+
+        let items = (0..rng.below(4)).map(|_| packet(rng, depth + 1));
+
+        super::Item::List(items.collect())
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let packets = (0..(2 * rng.below(5)))
+                .map(|_| packet(rng, 0))
+                .collect::<Vec<_>>();
+            let output = super::Joined(&packets, "\n").to_string();
+
+            super::prop::equal(&super::parse(&output)?, &packets)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(30, 10);
+    }
+}
@@ -0,0 +1,412 @@
+//! Day 14: Regolith Reservoir
+
+use util::*;
+
+#[cfg(feature = "reference")]
+pub mod reference;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Item {
+    Air,
+    Rock,
+    Sand,
+    Start,
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Item::Air => write!(f, "."),
+            Item::Rock => write!(f, "#"),
+            Item::Sand => write!(f, "o"),
+            Item::Start => write!(f, "+"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Puzzle {
+    pub map: Grid<Item>,
+}
+
+// Keeps the rock map, and the sand piling up on it, to a reasonable size
+const LIMIT: isize = 1 << 10;
+
+const SOURCE: Point2 = Point2::new(500, 0);
+
+fn parse_coord(input: &str, s: &str) -> Result<isize, ParseError> {
+    let n = s.parse::<isize>().at(input, s)?;
+
+    if !(-LIMIT..=LIMIT).contains(&n) {
+        return Err(ParseError::new(input, s, "Coordinate out of range"));
+    }
+
+    Ok(n)
+}
+
+/// Where sand at `p` falls next, if anywhere
+fn fall(map: &Grid<Item>, p: Point2) -> Option<Point2> {
+    [(0, 1), (-1, 1), (1, 1)]
+        .map(|step| p + step.into())
+        .into_iter()
+        .find(|&next| map.get(next).is_none_or(|&i| i == Item::Air))
+}
+
+/// Where a grain of sand from the source comes to rest, or `None` if it
+/// falls past `max_y`, unless there's a `floor` below to stop it
+fn pour(map: &Grid<Item>, max_y: isize, floor: bool) -> Option<Point2> {
+    let mut p = SOURCE;
+
+    while let Some(next) = fall(map, p) {
+        if next.y > max_y {
+            return floor.then_some(p);
+        }
+
+        p = next;
+    }
+
+    Some(p)
+}
+
+impl Puzzle {
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut segments = Vec::new();
+
+        for line in input.trim().lines() {
+            let mut prev: Option<Point2> = None;
+
+            for segment in line.trim().split(" -> ") {
+                let (x, y) = segment
+                    .split_once(',')
+                    .context("Missing y")
+                    .at(input, segment)?;
+                let p = Point2::new(parse_coord(input, x)?, parse_coord(input, y)?);
+
+                if let Some(prev) = prev.take() {
+                    segments.push((prev, p));
+                }
+
+                prev = Some(p);
+            }
+        }
+
+        let ends = segments.iter().flat_map(|&(a, b)| [a, b]);
+        let (min, max) = Point2::bounds(ends.chain([SOURCE])).unwrap_or_default();
+        let mut map = Grid::with_bounds(min, max, Item::Air);
+
+        for (a, b) in segments {
+            for x in a.x.min(b.x)..=a.x.max(b.x) {
+                for y in a.y.min(b.y)..=a.y.max(b.y) {
+                    map[Point2::new(x, y)] = Item::Rock;
+                }
+            }
+        }
+
+        map[SOURCE] = Item::Start;
+
+        Ok(Puzzle { map })
+    }
+
+    /// Sand pouring into the cave, onto a floor in part 2
+    pub fn cave(&self, floor: bool) -> Cave {
+        let mut map = self.map.clone();
+        let mut max_y = map.bounds().1.y;
+
+        if floor {
+            // Sand settles in a triangle above the floor at `max_y + 2`
+            max_y += 1;
+            map.include(SOURCE + Point2::new(-max_y, max_y), Item::Air);
+            map.include(SOURCE + Point2::new(max_y, max_y), Item::Air);
+        }
+
+        Cave {
+            max_y,
+            floor,
+            pile: Pile {
+                map,
+                grains: 0,
+                flowing: true,
+            },
+        }
+    }
+
+    pub fn part_1(&self) -> Result<usize, Error> {
+        let mut cave = self.cave(false);
+
+        while cave.step()? {}
+
+        Ok(cave.pile.grains)
+    }
+
+    pub fn part_2(&self) -> Result<usize, Error> {
+        let mut cave = self.cave(true);
+
+        while cave.step()? {}
+
+        Ok(cave.pile.grains)
+    }
+}
+
+/// Map of the cave with the sand that came to rest so far
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pile {
+    pub map: Grid<Item>,
+    pub grains: usize,
+    /// Whether more sand can still come to rest
+    pub flowing: bool,
+}
+
+/// Cave filling up with sand, a grain at a time
+pub struct Cave {
+    max_y: isize,
+    floor: bool,
+    pile: Pile,
+}
+
+impl Simulation for Cave {
+    type State = Pile;
+
+    fn step(&mut self) -> Result<bool, Error> {
+        let pile = &mut self.pile;
+
+        if !pile.flowing {
+            return Ok(false);
+        }
+
+        match pour(&pile.map, self.max_y, self.floor) {
+            // Fell into the abyss
+            None => pile.flowing = false,
+            Some(p) => {
+                pile.map[p] = Item::Sand;
+                pile.grains += 1;
+                // Sand piled up to the source
+                pile.flowing = p != SOURCE;
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn state(&self) -> &Self::State {
+        &self.pile
+    }
+
+    fn restore(&mut self, snapshot: Self::State) {
+        self.pile = snapshot;
+    }
+}
+
+impl fmt::Display for Puzzle {
+    /// Writes each horizontal run of rock as its own path
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut runs = Vec::new();
+
+        for (p, &item) in self.map.iter() {
+            match runs.last_mut() {
+                Some((_, end)) if item == Item::Rock && *end + Point2::new(1, 0) == p => *end = p,
+                _ if item == Item::Rock => runs.push((p, p)),
+                _ => {}
+            }
+        }
+
+        let paths = runs.iter().map(|(a, b)| format!("{a} -> {b}"));
+
+        write!(f, "{}", paths.collect::<Vec<_>>().join("\n"))
+    }
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 14;
+
+    type Input<'a> = Puzzle;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        Puzzle::parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        input.part_1()
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        input.part_2()
+    }
+}
+
+impl Visualize for Day {
+    /// Sand of part 2 piling up on the floor, a frame every few grains
+    fn visualize(input: &Self::Input<'_>, recorder: &mut dyn viz::Recorder) -> Result<(), Error> {
+        let mut cave = input.cave(true);
+        let color = |item: &Item| match item {
+            Item::Air => viz::Rgb(20, 20, 30),
+            Item::Rock => viz::Rgb(120, 110, 100),
+            Item::Sand => viz::Rgb(230, 190, 90),
+            Item::Start => viz::Rgb(255, 60, 40),
+        };
+        // At most the whole triangle fills up, so that's about 200 frames
+        let every = (cave.max_y * cave.max_y / 200).max(1) as usize;
+
+        while cave.step()? {
+            let pile = cave.state();
+
+            if pile.grains % every == 0 || !pile.flowing {
+                recorder.record(&viz::Image::from_grid(&pile.map, color))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 150;
+
+    /// `size` paths of rock, each a few horizontal or vertical segments,
+    /// in a cave about as deep
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let depth = size.clamp(10, 500) as isize;
+        let paths = (0..size).map(|_| {
+            let mut p = Point2::new(rng.range(450..=550), rng.range(1..=depth));
+            let mut path = vec![p];
+
+            for _ in 0..=rng.below(4) {
+                match rng.below(2) {
+                    0 => p.x = (p.x + rng.range(-8..=8)).clamp(400, 600),
+                    _ => p.y = (p.y + rng.range(-8..=8)).clamp(1, depth),
+                }
+
+                path.push(p);
+            }
+
+            path.iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        });
+
+        paths.collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        498,4 -> 498,6 -> 496,6
+        503,4 -> 502,4 -> 502,9 -> 494,9
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::Puzzle::parse(INPUT)?;
+
+        assert_eq!(input.part_1()?, 24);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::Puzzle::parse(INPUT)?;
+
+        assert_eq!(input.part_2()?, 93);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = super::Puzzle::parse("498,4 -> 498,6\n503,4 -> 5024\n").unwrap_err();
+        let err = err.downcast_ref::<super::ParseError>();
+
+        assert_eq!(err.map(|e| (e.line, e.columns.clone())), Some((2, 10..14)));
+        assert_eq!(err.map(|e| e.message.as_str()), Some("Missing y"));
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["498,4 -> 498", "x,1 -> 2,2", "498,4 -> 498503,4"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn blocked_source() -> Result<(), super::Error> {
+        let input = super::Puzzle::parse("499,1 -> 501,1")?;
+
+        assert_eq!(input.part_1()?, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn visualize() -> Result<(), super::Error> {
+        use super::viz::{Frame, Image, Rgb};
+
+        let input = super::Puzzle::parse(INPUT)?;
+        let mut frames = Vec::<Image>::new();
+
+        <super::Day as super::Visualize>::visualize(&input, &mut frames)?;
+
+        let last = frames.last().map(|frame| {
+            let pixels = (0..frame.height()).flat_map(|y| (0..frame.width()).map(move |x| (x, y)));
+
+            pixels
+                .filter(|&(x, y)| frame.pixel(x, y) == Rgb(230, 190, 90))
+                .count()
+        });
+
+        // A frame per grain, up to the one blocking the source
+        assert_eq!(frames.len(), 93);
+        assert_eq!(last, Some(93));
+
+        Ok(())
+    }
+
+    #[test]
+    fn replay() -> Result<(), super::Error> {
+        let input = super::Puzzle::parse(INPUT)?;
+
+        super::sim::check_replay(&mut input.cave(false), 30);
+        super::sim::check_replay(&mut input.cave(true), 100);
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let mut text = String::new();
+
+            for _ in 0..rng.below(5) {
+                let mut p = (rng.range(490..=510), rng.range(1..=20));
+
+                text += &format!("{},{}", p.0, p.1);
+
+                for _ in 0..=rng.below(3) {
+                    match rng.below(2) {
+                        0 => p.0 += rng.range(-5..=5),
+                        _ => p.1 += rng.range(0..=5),
+                    }
+
+                    text += &format!(" -> {},{}", p.0, p.1);
+                }
+
+                text += "\n";
+            }
+
+            let input = super::Puzzle::parse(&text)?;
+            let output = input.to_string();
+
+            super::prop::equal(&super::Puzzle::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(20, 10);
+    }
+}
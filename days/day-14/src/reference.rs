@@ -0,0 +1,75 @@
+//! Slow but obviously correct solutions, dropping every grain onto an
+//! unbounded set of blocked positions
+
+use util::*;
+
+use crate::{Item, Puzzle, SOURCE};
+
+fn sand(input: &Puzzle, floor: bool) -> usize {
+    let mut blocked = input
+        .map
+        .iter()
+        .filter(|&(_, &item)| item == Item::Rock)
+        .map(|(p, _)| p)
+        .collect::<HashSet<_>>();
+    let max_y = blocked.iter().map(|p| p.y).max().unwrap_or(0).max(SOURCE.y);
+    let mut grains = 0;
+
+    while !blocked.contains(&SOURCE) {
+        let is_blocked = |p: &Point2| blocked.contains(p) || floor && p.y == max_y + 2;
+        let mut p = SOURCE;
+
+        loop {
+            if !floor && p.y > max_y {
+                return grains;
+            }
+
+            let next = [(0, 1), (-1, 1), (1, 1)]
+                .map(|step| p + step.into())
+                .into_iter()
+                .find(|next| !is_blocked(next));
+
+            match next {
+                Some(next) => p = next,
+                None => break,
+            }
+        }
+
+        blocked.insert(p);
+        grains += 1;
+    }
+
+    grains
+}
+
+pub fn part_1(input: &Puzzle) -> Result<usize, Error> {
+    Ok(sand(input, false))
+}
+
+pub fn part_2(input: &Puzzle) -> Result<usize, Error> {
+    Ok(sand(input, true))
+}
+
+#[cfg(test)]
+mod test {
+    use util::differential::{check, disagree};
+    use util::Generate;
+
+    #[test]
+    fn differential() {
+        check(
+            50,
+            |rng| {
+                let size = rng.below(20) + 1;
+
+                <crate::Day as Generate>::generate(rng, size)
+            },
+            |text| {
+                let input = crate::Puzzle::parse(text).ok()?;
+
+                disagree("part 1", input.part_1(), super::part_1(&input))
+                    .or_else(|| disagree("part 2", input.part_2(), super::part_2(&input)))
+            },
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Day 15: Beacon Exclusion Zone
+
+use util::parser::{int, prefixed, Parser};
+use util::*;
+
+#[cfg(feature = "reference")]
+pub mod reference;
+
+/// Closest beacon to each sensor
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Puzzle {
+    pub beacons: HashMap<Point2, Point2>,
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut reports = self.beacons.iter().collect::<Vec<_>>();
+
+        reports.sort();
+
+        for (i, (sensor, beacon)) in reports.into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(
+                f,
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                sensor.x, sensor.y, beacon.x, beacon.y
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Keeps distances and tuning frequencies from overflowing
+const LIMIT: isize = 1 << 40;
+
+fn coord<'a>() -> impl Parser<'a, isize> {
+    int().try_map(|n: isize| {
+        if (-LIMIT..=LIMIT).contains(&n) {
+            Ok(n)
+        } else {
+            Err("Coordinate out of range")
+        }
+    })
+}
+
+fn point<'a>() -> impl Parser<'a, Point2> {
+    (prefixed("x=", coord()), prefixed(", y=", coord())).map(|(x, y)| Point2::new(x, y))
+}
+
+pub fn parse(input: &str) -> Result<Puzzle, Error> {
+    let report = (
+        prefixed("Sensor at", point()),
+        prefixed(": closest beacon is at", point()),
+    );
+    let reports = parser::run(input, input, parser::many_to_end(report))?;
+
+    Ok(Puzzle {
+        beacons: reports.into_iter().collect(),
+    })
+}
+
+/// Positions on row `y` within range of some sensor
+fn coverage(y: isize, input: &Puzzle) -> IntervalSet {
+    input
+        .beacons
+        .iter()
+        .filter_map(|(sensor, beacon)| {
+            let d = sensor.manhattan(*beacon);
+            let d = d.checked_sub(y.abs_diff(sensor.y))? as isize;
+
+            Some((sensor.x - d)..=(sensor.x + d))
+        })
+        .collect()
+}
+
+/// Positions on row `y` between 0 and `max` that no sensor can see
+pub fn gaps(y: isize, max: isize, input: &Puzzle) -> Vec<ops::RangeInclusive<isize>> {
+    coverage(y, input).gaps(0..=max).collect()
+}
+
+pub fn part_1(y: isize, input: &Puzzle) -> Result<usize, Error> {
+    let beacons = input
+        .beacons
+        .values()
+        .filter(|beacon| beacon.y == y)
+        .collect::<HashSet<_>>()
+        .len();
+
+    Ok(coverage(y, input).len() - beacons)
+}
+
+fn contained(max: isize, dist: &[(Point2, usize)], p: Point2) -> bool {
+    if p.x < 0 || p.y < 0 || p.x > max || p.y > max {
+        return true;
+    }
+
+    dist.iter().any(|&(sensor, d)| sensor.manhattan(p) <= d)
+}
+
+pub fn part_2(max: isize, input: &Puzzle) -> Result<isize, Error> {
+    let dist = input
+        .beacons
+        .iter()
+        .map(|(sensor, beacon)| (*sensor, sensor.manhattan(*beacon)))
+        .collect::<Vec<_>>();
+
+    for &(sensor, d) in &dist {
+        let d = d as isize;
+
+        // Points just out of range, at the tips of the sensor's diamond
+        let tips = Direction::ALL.map(|dir| sensor + dir.step() * (d + 1));
+
+        if dist
+            .iter()
+            .any(|&(other, d)| tips.iter().all(|&tip| other.manhattan(tip) <= d))
+        {
+            continue;
+        }
+
+        for x in (sensor.x - d - 1)..=(sensor.x + d + 1) {
+            let dx = sensor.x.abs_diff(x) as isize;
+            let dy = d - dx;
+
+            for y in [sensor.y - dy - 1, sensor.y + dy + 1] {
+                if !contained(max, &dist, Point2::new(x, y)) {
+                    return Ok(x * 4000000 + y);
+                }
+            }
+        }
+    }
+
+    bail!("No solution")
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 15;
+
+    type Input<'a> = Puzzle;
+    type Output1 = usize;
+    type Output2 = isize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(2000000, input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(4000000, input)
+    }
+}
+
+/// Sensors leaving exactly one position between 0 and `max` unseen, which
+/// is returned along with them
+fn field(rng: &mut Rng, size: usize, max: isize) -> (Puzzle, Point2) {
+    let gap = Point2::new(rng.range(0..=max), rng.range(0..=max));
+    let mut beacons = HashMap::new();
+
+    // Four sensors diagonally around the gap, each with a beacon next to it,
+    // already see every other position within `a` of it on both axes
+    let a = max + rng.range(1..=max / 4 + 1);
+
+    for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+        beacons.insert(gap + Point2::new(a * dx, a * dy), gap + Point2::new(dx, 0));
+    }
+
+    // More sensors that stop short of the gap, if they fit in with the rest
+    for _ in 0..size * 10 {
+        if beacons.len() >= size + 4 {
+            break;
+        }
+
+        let spread = max / 4 + 1;
+        let sensor = Point2::new(
+            rng.range(-spread..=max + spread),
+            rng.range(-spread..=max + spread),
+        );
+        let d = sensor.manhattan(gap) as isize - 1;
+
+        if d < 1 || beacons.contains_key(&sensor) {
+            continue;
+        }
+
+        let d = rng.range(d / 2..=d);
+        let dx = rng.range(-d..=d);
+        let dy = (d - dx.abs()) * if rng.chance(0.5) { 1 } else { -1 };
+        let beacon = sensor + Point2::new(dx, dy);
+
+        // Every sensor's beacon must stay its closest one
+        if beacons.iter().all(|(s, b)| {
+            s.manhattan(beacon) >= s.manhattan(*b) && sensor.manhattan(*b) >= d as usize
+        }) {
+            beacons.insert(sensor, beacon);
+        }
+    }
+
+    (Puzzle { beacons }, gap)
+}
+
+impl Generate for Day {
+    const SIZE: usize = 30;
+
+    /// `size` sensors and a few more around a single gap
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        field(rng, size, 4000000).0.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+        Sensor at x=9, y=16: closest beacon is at x=10, y=16
+        Sensor at x=13, y=2: closest beacon is at x=15, y=3
+        Sensor at x=12, y=14: closest beacon is at x=10, y=16
+        Sensor at x=10, y=20: closest beacon is at x=10, y=16
+        Sensor at x=14, y=17: closest beacon is at x=10, y=16
+        Sensor at x=8, y=7: closest beacon is at x=2, y=10
+        Sensor at x=2, y=0: closest beacon is at x=2, y=10
+        Sensor at x=0, y=11: closest beacon is at x=2, y=10
+        Sensor at x=20, y=14: closest beacon is at x=25, y=17
+        Sensor at x=17, y=20: closest beacon is at x=21, y=22
+        Sensor at x=16, y=7: closest beacon is at x=15, y=3
+        Sensor at x=14, y=3: closest beacon is at x=15, y=3
+        Sensor at x=20, y=1: closest beacon is at x=15, y=3
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(10, &input)?, 26);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(20, &input)?, 56000011);
+
+        Ok(())
+    }
+
+    #[test]
+    fn gaps() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::gaps(11, 20, &input), [14..=14]);
+        assert_eq!(super::gaps(10, 20, &input), []);
+        assert_eq!(super::gaps(10, 30, &input), [25..=30]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in [
+            "",
+            "Sensor at x=1, y=2",
+            "Sensor at x=99999999999999, y=0: closest beacon is at x=0, y=0",
+        ] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let text = (0..rng.below(10))
+                .map(|_| {
+                    let [a, b, c, d] = [(); 4].map(|_| rng.range(-100..=100));
+
+                    format!("Sensor at x={a}, y={b}: closest beacon is at x={c}, y={d}\n")
+                })
+                .collect::<String>();
+            let input = super::parse(&text)?;
+            let output = input.to_string();
+
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn field() {
+        super::prop::check(|rng| {
+            let (input, gap) = super::field(rng, 10, 20);
+
+            super::prop::equal(&super::part_2(20, &input)?, &(gap.x * 4000000 + gap.y))
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(5, 3);
+    }
+}
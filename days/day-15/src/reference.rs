@@ -0,0 +1,72 @@
+//! Slow but obviously correct solutions, checking every position
+
+use util::*;
+
+use crate::Puzzle;
+
+fn seen(input: &Puzzle, p: Point2) -> bool {
+    input
+        .beacons
+        .iter()
+        .any(|(sensor, beacon)| sensor.manhattan(p) <= sensor.manhattan(*beacon))
+}
+
+pub fn part_1(y: isize, input: &Puzzle) -> Result<usize, Error> {
+    let reach = input.beacons.iter().map(|(sensor, beacon)| {
+        let d = sensor.manhattan(*beacon) as isize;
+
+        (sensor.x - d, sensor.x + d)
+    });
+    let min = reach.clone().map(|(min, _)| min).min().unwrap_or(0);
+    let max = reach.map(|(_, max)| max).max().unwrap_or(-1);
+
+    Ok((min..=max)
+        .map(|x| Point2::new(x, y))
+        .filter(|&p| seen(input, p) && !input.beacons.values().any(|&b| b == p))
+        .count())
+}
+
+pub fn part_2(max: isize, input: &Puzzle) -> Result<isize, Error> {
+    let mut unseen = (0..=max)
+        .flat_map(|x| (0..=max).map(move |y| Point2::new(x, y)))
+        .filter(|&p| !seen(input, p));
+
+    match (unseen.next(), unseen.next()) {
+        (Some(p), None) => Ok(p.x * 4000000 + p.y),
+        (None, _) => bail!("No solution"),
+        (Some(_), Some(_)) => bail!("More than one solution"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use util::differential::{check, disagree};
+
+    #[test]
+    fn differential() {
+        check(
+            100,
+            |rng| {
+                let size = rng.below(8);
+
+                crate::field(rng, size, 20).0.to_string()
+            },
+            |text| {
+                let input = crate::parse(text).ok()?;
+
+                disagree(
+                    "part 1",
+                    crate::part_1(10, &input),
+                    super::part_1(10, &input),
+                )
+                .or_else(|| {
+                    disagree(
+                        "part 2",
+                        crate::part_2(20, &input),
+                        super::part_2(20, &input),
+                    )
+                })
+            },
+        );
+    }
+}
@@ -0,0 +1,419 @@
+//! Day 16: Proboscidea Volcanium
+
+use util::parser::{int, list, prefixed, tag, word, Parser};
+use util::*;
+
+#[cfg(feature = "reference")]
+pub mod reference;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Valve<'a> {
+    pub flow_rate: usize,
+    pub tunnels: HashSet<&'a str>,
+    /// Minutes to walk to each valve with any flow
+    pub dist: HashMap<&'a str, usize>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Puzzle<'a> {
+    pub valves: HashMap<&'a str, Valve<'a>>,
+}
+
+impl fmt::Display for Puzzle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut valves = self.valves.iter().collect::<Vec<_>>();
+
+        valves.sort_by_key(|&(name, _)| name);
+
+        for (i, (name, valve)) in valves.into_iter().enumerate() {
+            let mut tunnels = valve.tunnels.iter().copied().collect::<Vec<_>>();
+
+            tunnels.sort();
+
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "Valve {name} has flow rate={}; ", valve.flow_rate)?;
+
+            match tunnels[..] {
+                [tunnel] => write!(f, "tunnel leads to valve {tunnel}")?,
+                _ => write!(f, "tunnels lead to valves {}", tunnels.join(", "))?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn calc_dist<'a>(start: &'a str, valves: &HashMap<&'a str, Valve<'a>>) -> HashMap<&'a str, usize> {
+    let mut dist = graph::bfs(start, |name| {
+        valves
+            .get(name)
+            .into_iter()
+            .flat_map(|valve| valve.tunnels.iter().copied())
+    })
+    .dist;
+
+    dist.retain(|name, _| valves.get(name).map(|v| v.flow_rate > 0).unwrap_or(false));
+    dist
+}
+
+pub fn parse(input: &str) -> Result<Puzzle<'_>, Error> {
+    // Bounded so pressure totals can't overflow
+    let flow_rate = int::<u32>().map(|rate| rate as usize);
+    let tunnels = tag("tunnels lead to valves").or(tag("tunnel leads to valve"));
+    let valve = (
+        prefixed("Valve", word()),
+        prefixed("has flow rate=", flow_rate),
+        prefixed(";", tunnels),
+        list(word(), ","),
+    )
+        .map(|(name, flow_rate, _, tunnels)| {
+            let valve = Valve {
+                flow_rate,
+                tunnels: tunnels.into_iter().collect(),
+                dist: HashMap::with_capacity(0),
+            };
+
+            (name, valve)
+        });
+
+    let mut valves = parser::run(input, input, parser::many_to_end(valve))?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+    let distances = valves
+        .keys()
+        .map(|name| (*name, calc_dist(name, &valves)))
+        .collect::<HashMap<_, _>>();
+
+    for (name, d) in distances {
+        if let Some(valve) = valves.get_mut(name) {
+            valve.dist = d;
+        }
+    }
+
+    Ok(Puzzle { valves })
+}
+
+fn find_part_1<'a>(
+    puzzle: &Puzzle<'a>,
+    name: &'a str,
+    valve: &Valve<'a>,
+    mut remaining: usize,
+    mut sum: usize,
+    max: &mut usize,
+    visited: &mut HashSet<&'a str>,
+) {
+    visited.insert(name);
+
+    if valve.flow_rate > 0 {
+        remaining -= 1;
+        sum += remaining * valve.flow_rate;
+
+        if sum > *max {
+            *max = sum;
+        }
+    }
+
+    for (tunnel, dist) in &valve.dist {
+        if visited.contains(tunnel) || *dist >= remaining {
+            continue;
+        }
+
+        if let Some(valve) = puzzle.valves.get(tunnel) {
+            find_part_1(puzzle, tunnel, valve, remaining - dist, sum, max, visited);
+        }
+    }
+
+    visited.remove(name);
+}
+
+pub fn part_1(puzzle: &Puzzle) -> Result<usize, Error> {
+    let start_pos = "AA";
+    let mut max = 0;
+    let start = puzzle
+        .valves
+        .get(start_pos)
+        .with_context(|| format!("Missing valve {start_pos}"))?;
+
+    let mut visited = HashSet::new();
+
+    find_part_1(puzzle, start_pos, start, 30, 0, &mut max, &mut visited);
+
+    Ok(max)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn find_part_2<'a>(
+    puzzle: &Puzzle<'a>,
+    name: &'a str,
+    valve: &Valve<'a>,
+    mut remaining: usize,
+    mut sum: usize,
+    max: &mut usize,
+    visited: &mut HashSet<&'a str>,
+    is_elephant: bool,
+) {
+    // The elephant sets off from AA, which the first path already opened
+    let opened = visited.insert(name);
+
+    if opened && valve.flow_rate > 0 {
+        remaining -= 1;
+        sum += remaining * valve.flow_rate;
+
+        if sum > *max {
+            *max = sum;
+        }
+    }
+
+    if !is_elephant {
+        if let Some(valve) = puzzle.valves.get("AA") {
+            find_part_2(puzzle, "AA", valve, 26, sum, max, visited, true);
+        }
+    }
+
+    for (tunnel, dist) in &valve.dist {
+        if visited.contains(tunnel) || *dist >= remaining {
+            continue;
+        }
+
+        if let Some(valve) = puzzle.valves.get(tunnel) {
+            find_part_2(
+                puzzle,
+                tunnel,
+                valve,
+                remaining - dist,
+                sum,
+                max,
+                visited,
+                is_elephant,
+            );
+        }
+    }
+
+    if opened {
+        visited.remove(name);
+    }
+}
+
+pub fn part_2(puzzle: &Puzzle) -> Result<usize, Error> {
+    let start_pos = "AA";
+    let mut max = 0;
+    let start = puzzle
+        .valves
+        .get(start_pos)
+        .with_context(|| format!("Missing valve {start_pos}"))?;
+
+    let mut visited = HashSet::new();
+
+    find_part_2(
+        puzzle,
+        start_pos,
+        start,
+        26,
+        0,
+        &mut max,
+        &mut visited,
+        false,
+    );
+
+    Ok(max)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 16;
+
+    type Input<'a> = Puzzle<'a>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 60;
+
+    /// `size` valves, at most 15 of them working, joined by corridors of
+    /// broken valves like real inputs so the search stays feasible
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let count = size.clamp(2, 26 * 26);
+        let mut names = (1..26 * 26)
+            .map(|i| {
+                let [a, b] = [i / 26, i % 26].map(|c| char::from(b'A' + c as u8));
+
+                format!("{a}{b}")
+            })
+            .collect::<Vec<_>>();
+
+        rng.shuffle(&mut names);
+        names.insert(0, "AA".to_string());
+
+        // AA and the working valves, joined by a random tree and a few
+        // shortcuts
+        let hubs = (count / 4).clamp(1, 15) + 1;
+        let mut edges = (1..hubs).map(|i| (i, rng.below(i))).collect::<Vec<_>>();
+
+        for _ in 0..hubs / 3 {
+            let (a, b) = (rng.below(hubs), rng.below(hubs));
+
+            if a != b && !edges.contains(&(a, b)) && !edges.contains(&(b, a)) {
+                edges.push((a, b));
+            }
+        }
+
+        // The other valves spread along the edges, at least one each where
+        // there are enough
+        let mut corridors = vec![0; edges.len()];
+
+        for i in 0..count - hubs {
+            match corridors.get_mut(i) {
+                Some(len) => *len += 1,
+                None => corridors[rng.below(edges.len())] += 1,
+            }
+        }
+
+        let mut tunnels = vec![Vec::new(); count];
+        let mut next = hubs;
+
+        for ((a, b), len) in edges.into_iter().zip(corridors) {
+            let path = iter::once(a)
+                .chain(next..next + len)
+                .chain(iter::once(b))
+                .collect::<Vec<_>>();
+
+            next += len;
+
+            for pair in path.windows(2) {
+                tunnels[pair[0]].push(names[pair[1]].as_str());
+                tunnels[pair[1]].push(names[pair[0]].as_str());
+            }
+        }
+
+        let valves = tunnels
+            .iter_mut()
+            .enumerate()
+            .map(|(i, tunnels)| {
+                let flow_rate = if (1..hubs).contains(&i) {
+                    rng.below(23) + 3
+                } else {
+                    0
+                };
+
+                let tunnels = match tunnels[..] {
+                    [tunnel] => format!("tunnel leads to valve {tunnel}"),
+                    _ => {
+                        tunnels.sort();
+                        format!("tunnels lead to valves {}", tunnels.join(", "))
+                    }
+                };
+
+                format!("Valve {} has flow rate={flow_rate}; {tunnels}", names[i])
+            })
+            .collect::<Vec<_>>();
+
+        valves.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+        Valve BB has flow rate=13; tunnels lead to valves CC, AA
+        Valve CC has flow rate=2; tunnels lead to valves DD, BB
+        Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+        Valve EE has flow rate=3; tunnels lead to valves FF, DD
+        Valve FF has flow rate=0; tunnels lead to valves EE, GG
+        Valve GG has flow rate=0; tunnels lead to valves FF, HH
+        Valve HH has flow rate=22; tunnel leads to valve GG
+        Valve II has flow rate=0; tunnels lead to valves AA, JJ
+        Valve JJ has flow rate=21; tunnel leads to valve II
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 1651);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 1707);
+
+        Ok(())
+    }
+
+    // Only one of us gets to open AA
+    #[test]
+    fn open_start() -> Result<(), super::Error> {
+        let input = INPUT.replace("AA has flow rate=0", "AA has flow rate=5");
+        let input = super::parse(&input)?;
+
+        assert_eq!(super::part_2(&input)?, 1796);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in [
+            "",
+            "Valve BB has flow rate=1; tunnel leads to valve BB",
+            "Valve AA has flow rate=99999999999; tunnel leads to valve AA",
+        ] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let names = ["AA", "BB", "CC", "DD", "EE", "FF", "GG", "HH"];
+            let count = rng.below(names.len()) + 1;
+            let text = names[..count]
+                .iter()
+                .map(|name| {
+                    let tunnels = (0..=rng.below(3))
+                        .map(|_| *rng.choose(&names[..count]))
+                        .collect::<super::HashSet<_>>()
+                        .into_iter()
+                        .collect::<Vec<_>>();
+
+                    format!(
+                        "Valve {name} has flow rate={}; tunnels lead to valves {}\n",
+                        rng.below(25),
+                        tunnels.join(", ")
+                    )
+                })
+                .collect::<String>();
+            let input = super::parse(&text)?;
+            let output = input.to_string();
+
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(24, 10);
+    }
+}
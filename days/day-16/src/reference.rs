@@ -0,0 +1,118 @@
+//! Slow but obviously correct solutions, trying every move every minute
+
+use util::*;
+
+use crate::Puzzle;
+
+/// Most pressure released in `time` minutes from AA by the set of valves
+/// opened, the working ones numbered in the order of their names
+fn released(puzzle: &Puzzle, time: usize) -> Result<HashMap<u64, usize>, Error> {
+    if !puzzle.valves.contains_key("AA") {
+        bail!("Missing valve AA");
+    }
+
+    let mut working = puzzle
+        .valves
+        .iter()
+        .filter(|(_, valve)| valve.flow_rate > 0)
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>();
+
+    working.sort();
+
+    if working.len() > 16 {
+        bail!("Too many working valves");
+    }
+
+    // Pressure the opened valves will release by the end, for each position
+    // and set of opened valves
+    let mut states = HashMap::from([(("AA", 0u64), 0)]);
+
+    for minute in 1..=time {
+        let mut next = HashMap::new();
+
+        for ((name, opened), pressure) in states {
+            let Some(valve) = puzzle.valves.get(name) else {
+                continue;
+            };
+            let mut moves = vec![(name, opened, pressure)];
+
+            moves.extend(
+                valve
+                    .tunnels
+                    .iter()
+                    .map(|&tunnel| (tunnel, opened, pressure)),
+            );
+
+            if let Some(i) = working.iter().position(|&w| w == name) {
+                if opened & (1 << i) == 0 {
+                    let pressure = pressure + valve.flow_rate * (time - minute);
+
+                    moves.push((name, opened | 1 << i, pressure));
+                }
+            }
+
+            for (name, opened, pressure) in moves {
+                let best = next.entry((name, opened)).or_insert(0);
+
+                *best = (*best).max(pressure);
+            }
+        }
+
+        states = next;
+    }
+
+    let mut best = HashMap::new();
+
+    for ((_, opened), pressure) in states {
+        let entry = best.entry(opened).or_insert(0);
+
+        *entry = (*entry).max(pressure);
+    }
+
+    Ok(best)
+}
+
+pub fn part_1(puzzle: &Puzzle) -> Result<usize, Error> {
+    Ok(released(puzzle, 30)?.into_values().max().unwrap_or(0))
+}
+
+/// The elephant and I open separate valves, so try every way to split them
+pub fn part_2(puzzle: &Puzzle) -> Result<usize, Error> {
+    let best = released(puzzle, 26)?;
+    let mut max = 0;
+
+    for (&mine, &a) in &best {
+        for (&theirs, &b) in &best {
+            if mine & theirs == 0 {
+                max = max.max(a + b);
+            }
+        }
+    }
+
+    Ok(max)
+}
+
+#[cfg(test)]
+mod test {
+    use util::differential::{check, disagree};
+    use util::Generate;
+
+    #[test]
+    fn differential() {
+        check(
+            50,
+            |rng| {
+                let size = rng.below(24) + 1;
+
+                <crate::Day as Generate>::generate(rng, size)
+            },
+            |text| {
+                let input = crate::parse(text).ok()?;
+
+                disagree("part 1", crate::part_1(&input), super::part_1(&input))
+                    .or_else(|| disagree("part 2", crate::part_2(&input), super::part_2(&input)))
+            },
+        );
+    }
+}
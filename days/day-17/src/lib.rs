@@ -0,0 +1,412 @@
+//! Day 17: Pyroclastic Flow
+
+use util::*;
+
+#[cfg(feature = "reference")]
+pub mod reference;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+impl fmt::Display for Jet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Jet::Left => write!(f, "<"),
+            Jet::Right => write!(f, ">"),
+        }
+    }
+}
+
+const SHAPES: [&[(isize, isize)]; 5] = [
+    &[(0, 0), (1, 0), (2, 0), (3, 0)],
+    &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+    &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+    &[(0, 0), (0, 1), (0, 2), (0, 3)],
+    &[(0, 0), (1, 0), (0, 1), (1, 1)],
+];
+
+/// Rocks dropped in part 2
+const ROCKS: usize = 1_000_000_000_000;
+const DEPTH: usize = 64;
+
+fn next_shape(i: usize) -> Vec<Point2> {
+    SHAPES[i % SHAPES.len()].iter().map(|&p| p.into()).collect()
+}
+
+/// Empty chamber, seven wide between walls at `x = 0` and `x = 8`, with a
+/// floor at `y = 0` and `y` growing upwards
+fn chamber() -> Grid<bool> {
+    Grid::with_bounds(Point2::new(1, 0), Point2::new(7, 0), true)
+}
+
+fn is_free(board: &Grid<bool>, p: Point2) -> bool {
+    (1..=7).contains(&p.x) && !board.get(p).copied().unwrap_or(false)
+}
+
+fn simulate(
+    jets: &[Jet],
+    board: &mut Grid<bool>,
+    mut shape: Vec<Point2>,
+    j: &mut usize,
+    top: &mut usize,
+) {
+    for p in shape.iter_mut() {
+        *p += Point2::new(3, *top as isize + 4);
+    }
+
+    loop {
+        let push = match jets[*j % jets.len()] {
+            Jet::Left => Direction::Left,
+            Jet::Right => Direction::Right,
+        };
+        let next = shape.iter().map(|&p| p + push.step()).collect::<Vec<_>>();
+
+        *j += 1;
+
+        if next.iter().all(|&p| is_free(board, p)) {
+            shape = next;
+        }
+
+        let next = shape
+            .iter()
+            .map(|&p| p - Point2::new(0, 1))
+            .collect::<Vec<_>>();
+
+        if !next.iter().all(|&p| is_free(board, p)) {
+            break;
+        }
+
+        shape = next;
+    }
+
+    for &p in &shape {
+        *top = (*top).max(p.y as usize);
+        board.include(p, false);
+        board[p] = true;
+    }
+}
+
+/// Chamber and the rocks that came to rest in it
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stack {
+    pub board: Grid<bool>,
+    /// Height of the tower
+    pub top: usize,
+    /// Jets pushed so far, and rocks dropped
+    pub jets: usize,
+    pub rocks: usize,
+}
+
+/// Tower of rocks growing in the chamber, a rock at a time
+pub struct Tower<'a> {
+    jets: &'a [Jet],
+    stack: Stack,
+}
+
+impl<'a> Tower<'a> {
+    pub fn new(jets: &'a [Jet]) -> Result<Self, Error> {
+        if jets.is_empty() {
+            bail!("No jets");
+        }
+
+        Ok(Self {
+            jets,
+            stack: Stack {
+                board: chamber(),
+                top: 0,
+                jets: 0,
+                rocks: 0,
+            },
+        })
+    }
+}
+
+impl Simulation for Tower<'_> {
+    type State = Stack;
+
+    /// Drop a rock, of which there are always more
+    fn step(&mut self) -> Result<bool, Error> {
+        let Stack {
+            board,
+            top,
+            jets,
+            rocks,
+        } = &mut self.stack;
+
+        simulate(self.jets, board, next_shape(*rocks), jets, top);
+        *rocks += 1;
+
+        Ok(true)
+    }
+
+    fn state(&self) -> &Self::State {
+        &self.stack
+    }
+
+    fn restore(&mut self, snapshot: Self::State) {
+        self.stack = snapshot;
+    }
+}
+
+pub fn part_1(jets: &[Jet]) -> Result<usize, Error> {
+    let mut tower = Tower::new(jets)?;
+
+    tower.run_until(|stack| stack.rocks == 2022)?;
+
+    Ok(tower.stack.top)
+}
+
+/// Empty cells a falling rock could reach, down to `DEPTH` below the top,
+/// as a row of bits per depth
+///
+/// Rocks only move sideways and down, so the rest of the tower, overhangs
+/// included, never affects how later rocks fall, except below `DEPTH`.
+fn surface(board: &Grid<bool>, top: usize) -> [u8; DEPTH] {
+    let mut rows = [0u8; DEPTH];
+    let mut todo = (1..=7)
+        .map(|x| Point2::new(x, top as isize + 1))
+        .collect::<Vec<_>>();
+
+    while let Some(p) = todo.pop() {
+        let depth = (top as isize + 1 - p.y) as usize;
+
+        if depth >= DEPTH || !is_free(board, p) || rows[depth] & 1 << p.x != 0 {
+            continue;
+        }
+
+        rows[depth] |= 1 << p.x;
+        todo.extend([
+            p + Direction::Left.step(),
+            p + Direction::Right.step(),
+            p - Point2::new(0, 1),
+        ]);
+    }
+
+    rows
+}
+
+/// Height of the tower after `rocks` rocks, extrapolated from the first
+/// cycle in how they fall
+fn height(jets: &[Jet], rocks: usize) -> Result<usize, Error> {
+    let mut tower = Tower::new(jets)?;
+    let mut heights = Vec::new();
+    let mut detector = cycle::Detector::new();
+    // Rocks dropped before the detector last started over
+    let mut skipped = 0;
+    let mut found: Option<cycle::Cycle> = None;
+
+    loop {
+        let Stack {
+            board,
+            top,
+            jets: j,
+            rocks: i,
+        } = tower.state();
+
+        if *i == rocks {
+            return Ok(*top);
+        }
+
+        heights.push(*top);
+
+        // Wells deeper than `DEPTH` look alike, so a cycle only counts once
+        // the tower grows the same way over another period
+        if let Some(cycle) = found {
+            if heights.len() > cycle.start + 2 * cycle.period {
+                if cycle.confirm(&heights) {
+                    return cycle.extrapolate(&heights, rocks).context("Tower too tall");
+                }
+
+                detector = cycle::Detector::new();
+                skipped = *i;
+                found = None;
+            }
+        }
+
+        if found.is_none() {
+            let state = (i % SHAPES.len(), j % jets.len(), surface(board, *top));
+
+            found = detector.push(state).map(|cycle| cycle::Cycle {
+                start: cycle.start + skipped,
+                ..cycle
+            });
+        }
+
+        tower.step()?;
+    }
+}
+
+pub fn part_2(jets: &[Jet]) -> Result<usize, Error> {
+    height(jets, ROCKS)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Jet>, Error> {
+    input
+        .trim()
+        .char_indices()
+        .map(|(i, c)| match c {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            _ => {
+                let span = &input.trim()[i..(i + c.len_utf8())];
+
+                Err(ParseError::new(input, span, format!("Invalid char: {c}")).into())
+            }
+        })
+        .collect()
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 17;
+
+    type Input<'a> = Vec<Jet>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+/// Rows of the chamber shown when visualizing, up to where rocks appear
+const VIEW: usize = 40;
+
+impl Visualize for Day {
+    /// The rocks of part 1 piling up, a frame per rock, following the top
+    /// of the tower
+    fn visualize(input: &Self::Input<'_>, recorder: &mut dyn viz::Recorder) -> Result<(), Error> {
+        let mut tower = Tower::new(input)?;
+
+        while tower.state().rocks < 2022 {
+            tower.step()?;
+
+            let Stack { board, top, .. } = tower.state();
+
+            // Walls on either side, and the floor once in view
+            let mut frame = viz::Image::new(9, VIEW, viz::Rgb(20, 20, 30));
+            let view_top = (*top + 4).max(VIEW - 1);
+
+            for row in 0..VIEW {
+                let y = (view_top - row) as isize;
+
+                for x in 0..9 {
+                    let p = Point2::new(x, y);
+                    let color = if x == 0 || x == 8 || y == 0 {
+                        viz::Rgb(120, 110, 100)
+                    } else if board.get(p) == Some(&true) {
+                        viz::Rgb(200, 90, 40)
+                    } else {
+                        continue;
+                    };
+
+                    frame.set(Point2::new(x, row as isize), color);
+                }
+            }
+
+            recorder.record(&frame)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 10000;
+
+    /// `size` jets, pushing either way at random
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size.max(1)).map(|_| *rng.choose(&['<', '>'])).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 3068);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 1514285714288);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["", "<>x"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn visualize() -> Result<(), super::Error> {
+        use super::viz::{Frame, Image, Rgb};
+
+        let input = super::parse(INPUT)?;
+        let mut frames = Vec::<Image>::new();
+
+        <super::Day as super::Visualize>::visualize(&input, &mut frames)?;
+
+        assert_eq!(frames.len(), 2022);
+
+        // The first rock lies flat on the floor, at the bottom of the view
+        let row = |y| (0..9).map(|x| frames[0].pixel(x, y)).collect::<Vec<_>>();
+        let (wall, rock, air) = (Rgb(120, 110, 100), Rgb(200, 90, 40), Rgb(20, 20, 30));
+
+        assert_eq!(row(39), [wall; 9]);
+        assert_eq!(row(38), [wall, air, air, rock, rock, rock, rock, air, wall]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn replay() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        super::sim::check_replay(&mut super::Tower::new(&input)?, 50);
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let text = (0..rng.below(50))
+                .map(|_| *rng.choose(&['<', '>']))
+                .collect::<String>();
+            let input = super::parse(&text)?;
+            let output = super::Joined(&input, "").to_string();
+
+            super::prop::equal(&output, &text)?;
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(200, 5);
+    }
+}
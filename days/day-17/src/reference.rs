@@ -0,0 +1,84 @@
+//! Slow but obviously correct solutions, dropping every rock
+
+use util::*;
+
+use crate::{Jet, SHAPES};
+
+pub fn height(jets: &[Jet], rocks: usize) -> Result<usize, Error> {
+    if jets.is_empty() {
+        bail!("No jets");
+    }
+
+    let mut settled = HashSet::new();
+    let mut top = 0;
+    let mut jets = jets.iter().cycle();
+
+    for shape in SHAPES.iter().cycle().take(rocks) {
+        let fits = |x: isize, y: isize| {
+            shape.iter().all(|&(dx, dy)| {
+                (1..=7).contains(&(x + dx)) && y + dy > 0 && !settled.contains(&(x + dx, y + dy))
+            })
+        };
+        let (mut x, mut y) = (3, top + 4);
+
+        loop {
+            let dx = match jets.next() {
+                Some(Jet::Left) => -1,
+                _ => 1,
+            };
+
+            if fits(x + dx, y) {
+                x += dx;
+            }
+
+            if !fits(x, y - 1) {
+                break;
+            }
+
+            y -= 1;
+        }
+
+        for &(dx, dy) in shape.iter() {
+            settled.insert((x + dx, y + dy));
+            top = top.max(y + dy);
+        }
+    }
+
+    Ok(top as usize)
+}
+
+#[cfg(test)]
+mod test {
+    use util::differential::{check, disagree};
+
+    #[test]
+    fn differential() {
+        check(
+            30,
+            |rng| {
+                let size = match rng.below(3) {
+                    0 => rng.below(30) + 1,
+                    _ => rng.below(2000) + 1,
+                };
+                // Jets mostly pushing one way pile the rocks up against one
+                // wall, leaving deep wells along the other
+                let left = *rng.choose(&[0.5, 0.03, 0.1, 0.9, 0.97]);
+
+                (0..size)
+                    .map(|_| if rng.chance(left) { '<' } else { '>' })
+                    .collect()
+            },
+            |text| {
+                let jets = crate::parse(text).ok()?;
+
+                [2022, 4321, 20_000].into_iter().find_map(|rocks| {
+                    disagree(
+                        &format!("{rocks} rocks"),
+                        crate::height(&jets, rocks),
+                        super::height(&jets, rocks),
+                    )
+                })
+            },
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! Day 18: Boiling Boulders
+
+use util::*;
+
+#[cfg(feature = "reference")]
+pub mod reference;
+
+// Keeps neighbours and the fill's bounding box from overflowing
+const LIMIT: isize = 1 << 20;
+const MAX_VOLUME: usize = 1 << 24;
+
+pub fn parse(input: &str) -> Result<Vec<Point3>, Error> {
+    input
+        .trim()
+        .lines()
+        .map(|l| {
+            let l = l.trim();
+            let mut it = l.split(',');
+            let mut next = |axis| -> Result<isize, ParseError> {
+                let s = it
+                    .next()
+                    .with_context(|| format!("Missing {axis}"))
+                    .at(input, l)?;
+
+                let n = s.parse().at(input, s)?;
+
+                if !(-LIMIT..=LIMIT).contains(&n) {
+                    return Err(ParseError::new(input, s, "Coordinate out of range"));
+                }
+
+                Ok(n)
+            };
+
+            Ok(Point3::new(next("x")?, next("y")?, next("z")?))
+        })
+        .collect()
+}
+
+pub fn part_1(cubes: &[Point3]) -> Result<usize, Error> {
+    let cubes = cubes.iter().collect::<HashSet<_>>();
+
+    Ok(cubes
+        .iter()
+        .flat_map(|p| p.neighbors6())
+        .filter(|p| !cubes.contains(p))
+        .count())
+}
+
+pub fn part_2(cubes: &[Point3]) -> Result<usize, Error> {
+    let (min, max) = Point3::bounds(cubes.iter().copied()).context("No cubes")?;
+    let margin = Point3::new(2, 2, 2);
+    let (min, max) = (min - margin, max + margin);
+    let size = max - min;
+
+    let volume = [size.x, size.y, size.z]
+        .into_iter()
+        .try_fold(1usize, |acc, d| acc.checked_mul(d.unsigned_abs() + 1));
+
+    if volume.is_none_or(|volume| volume > MAX_VOLUME) {
+        bail!("Droplet too large to fill");
+    }
+
+    let cubes = cubes.iter().collect::<HashSet<_>>();
+    let mut filled = HashSet::new();
+    let mut surfaces = 0;
+    let mut stack = vec![min];
+
+    // Flood fill the air around the droplet, counting every face it touches
+    while let Some(point) = stack.pop() {
+        if !point.within(min, max) {
+            continue;
+        }
+
+        if filled.contains(&point) {
+            continue;
+        }
+
+        if cubes.contains(&point) {
+            surfaces += 1;
+            continue;
+        }
+
+        filled.insert(point);
+        stack.extend(point.neighbors6());
+    }
+
+    Ok(surfaces)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 18;
+
+    type Input<'a> = Vec<Point3>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+/// A squashed ball of about `size` cubes with sealed single-cube pockets of air,
+/// which are counted along with it
+fn droplet(rng: &mut Rng, size: usize) -> (Vec<Point3>, usize) {
+    let r = (size.clamp(1, 1_000_000) as f64 * 3.0 / (4.0 * std::f64::consts::PI)).cbrt();
+    let radii = [(); 3].map(|_| r * rng.range(80..=125) as f64 / 100.0);
+    let [cx, cy, cz] = radii.map(|r| r.ceil() as isize + 1);
+    let mut cubes = Vec::new();
+
+    for x in 0..=2 * cx {
+        for y in 0..=2 * cy {
+            for z in 0..=2 * cz {
+                let d = [x - cx, y - cy, z - cz]
+                    .into_iter()
+                    .zip(radii)
+                    .map(|(d, r)| (d as f64 / r).powi(2))
+                    .sum::<f64>();
+
+                if d <= 1.0 {
+                    cubes.push(Point3::new(x, y, z));
+                }
+            }
+        }
+    }
+
+    // Only cubes buried on all sides become pockets, never next to another
+    let solid = cubes.iter().copied().collect::<HashSet<_>>();
+    let mut pockets = HashSet::new();
+
+    for _ in 0..size / 50 {
+        let p = *rng.choose(&cubes);
+
+        if p.neighbors6()
+            .iter()
+            .all(|n| solid.contains(n) && !pockets.contains(n))
+        {
+            pockets.insert(p);
+        }
+    }
+
+    cubes.retain(|p| !pockets.contains(p));
+    rng.shuffle(&mut cubes);
+
+    (cubes, pockets.len())
+}
+
+impl Generate for Day {
+    const SIZE: usize = 3000;
+
+    /// About `size` cubes around some pockets of air
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        Joined(&droplet(rng, size).0, "\n").to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        2,2,2
+        1,2,2
+        3,2,2
+        2,1,2
+        2,3,2
+        2,2,1
+        2,2,3
+        2,2,4
+        2,2,6
+        1,2,5
+        3,2,5
+        2,1,5
+        2,3,5
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 64);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 58);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["", "1,2", "1,1,1\n1000000,1,1", "2000000,1,1"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let cubes = (0..rng.below(20))
+                .map(|_| {
+                    super::Point3::new(rng.range(-9..=9), rng.range(-9..=9), rng.range(-9..=9))
+                })
+                .collect::<Vec<_>>();
+            let output = super::Joined(&cubes, "\n").to_string();
+
+            super::prop::equal(&super::parse(&output)?, &cubes)
+        });
+    }
+
+    #[test]
+    fn pockets() {
+        super::prop::check_cases(20, |rng| {
+            let (cubes, pockets) = super::droplet(rng, 500);
+            let (outside, all) = (super::part_2(&cubes)?, super::part_1(&cubes)?);
+
+            super::prop::equal(&(all - outside), &(6 * pockets))
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(500, 10);
+    }
+}
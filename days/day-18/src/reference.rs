@@ -0,0 +1,71 @@
+//! Slow but obviously correct solutions, searching for a way out from every
+//! uncovered face
+
+use util::*;
+
+/// Whether air at `p` connects to the outside of the box from `min` to `max`
+fn escapes(cubes: &HashSet<Point3>, p: Point3, min: Point3, max: Point3) -> bool {
+    let mut seen = HashSet::from([p]);
+    let mut todo = vec![p];
+
+    while let Some(p) = todo.pop() {
+        if !p.within(min, max) {
+            return true;
+        }
+
+        for n in p.neighbors6() {
+            if !cubes.contains(&n) && seen.insert(n) {
+                todo.push(n);
+            }
+        }
+    }
+
+    false
+}
+
+pub fn part_1(cubes: &[Point3]) -> Result<usize, Error> {
+    let cubes = cubes.iter().copied().collect::<HashSet<_>>();
+
+    Ok(cubes
+        .iter()
+        .flat_map(|p| p.neighbors6())
+        .filter(|n| !cubes.contains(n))
+        .count())
+}
+
+pub fn part_2(cubes: &[Point3]) -> Result<usize, Error> {
+    let (min, max) = Point3::bounds(cubes.iter().copied()).context("No cubes")?;
+    let cubes = cubes.iter().copied().collect::<HashSet<_>>();
+
+    Ok(cubes
+        .iter()
+        .flat_map(|p| p.neighbors6())
+        .filter(|&n| !cubes.contains(&n) && escapes(&cubes, n, min, max))
+        .count())
+}
+
+#[cfg(test)]
+mod test {
+    use util::differential::{check, disagree};
+    use util::Point3;
+
+    #[test]
+    fn differential() {
+        check(
+            100,
+            |rng| {
+                // Packed densely enough into a small box to leave pockets
+                let cubes = (0..rng.below(80))
+                    .map(|_| Point3::new(rng.range(0..=5), rng.range(0..=5), rng.range(0..=5)));
+
+                cubes.map(|p| p.to_string()).collect::<Vec<_>>().join("\n")
+            },
+            |text| {
+                let cubes = crate::parse(text).ok()?;
+
+                disagree("part 1", crate::part_1(&cubes), super::part_1(&cubes))
+                    .or_else(|| disagree("part 2", crate::part_2(&cubes), super::part_2(&cubes)))
+            },
+        );
+    }
+}
@@ -0,0 +1,473 @@
+//! Day 19: Not Enough Minerals
+
+use std::num;
+
+use util::parser::{int, prefixed, tag, Parser};
+use util::*;
+
+#[cfg(feature = "reference")]
+pub mod reference;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
+pub struct Ore(pub usize);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
+pub struct Clay(pub usize);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
+pub struct Obsidian(pub usize);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
+pub struct Geode(pub usize);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
+struct Time(usize);
+
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Blueprint {
+    pub id: usize,
+    pub ore: Ore,
+    pub clay: Ore,
+    pub obsidian: (Ore, Clay),
+    pub geode: (Ore, Obsidian),
+    /// Most of each robot worth having, as no more can be spent in a minute
+    pub clay_robot_limit: Clay,
+    pub ore_robot_limit: Ore,
+}
+
+impl fmt::Display for Blueprint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Blueprint {}: \
+             Each ore robot costs {} ore. \
+             Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. \
+             Each geode robot costs {} ore and {} obsidian.",
+            self.id,
+            self.ore.0,
+            self.clay.0,
+            self.obsidian.0 .0,
+            self.obsidian.1 .0,
+            self.geode.0 .0,
+            self.geode.1 .0,
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct State {
+    time: Time,
+    ore: Ore,
+    clay: Clay,
+    obsidian: Obsidian,
+    geodes: Geode,
+    ore_robots: Ore,
+    clay_robots: Clay,
+    obsidian_robots: Obsidian,
+}
+
+fn div_ceil(lhs: usize, rhs: usize) -> usize {
+    let d = lhs / rhs;
+
+    if lhs.is_multiple_of(rhs) {
+        d
+    } else {
+        d + 1
+    }
+}
+
+impl State {
+    fn build_geode_robot(&self, blueprint: &Blueprint, max_geodes: &mut Geode) {
+        if self.obsidian_robots.0 == 0 {
+            return;
+        }
+
+        let is_building = self.ore >= blueprint.geode.0 && self.obsidian >= blueprint.geode.1;
+        let time_delta = if is_building {
+            1
+        } else {
+            let delta = div_ceil(
+                if self.ore > blueprint.geode.0 {
+                    0
+                } else {
+                    blueprint.geode.0 .0 - self.ore.0
+                },
+                self.ore_robots.0,
+            )
+            .max(div_ceil(
+                blueprint.geode.1 .0.saturating_sub(self.obsidian.0),
+                self.obsidian_robots.0,
+            ));
+
+            1 + delta
+        };
+
+        if self.time.0 < time_delta {
+            return;
+        }
+
+        round(
+            blueprint,
+            State {
+                time: Time(self.time.0 - time_delta),
+                ore: Ore(self.ore.0 + time_delta * self.ore_robots.0 - blueprint.geode.0 .0),
+                clay: Clay(self.clay.0 + time_delta * self.clay_robots.0),
+                obsidian: Obsidian(
+                    self.obsidian.0 + time_delta * self.obsidian_robots.0 - blueprint.geode.1 .0,
+                ),
+                geodes: Geode(self.geodes.0 + self.time.0 - time_delta),
+                ..self.clone()
+            },
+            max_geodes,
+        );
+    }
+
+    fn build_obsidian_robot(&self, blueprint: &Blueprint, max_geodes: &mut Geode) {
+        if self.clay_robots.0 == 0 {
+            return;
+        }
+
+        let is_building = self.ore >= blueprint.obsidian.0 && self.clay >= blueprint.obsidian.1;
+
+        let time_delta = if is_building {
+            1
+        } else {
+            let delta = div_ceil(
+                blueprint.obsidian.1 .0.saturating_sub(self.clay.0),
+                self.clay_robots.0,
+            )
+            .max(div_ceil(
+                blueprint.obsidian.0 .0.saturating_sub(self.ore.0),
+                self.ore_robots.0,
+            ));
+
+            1 + delta
+        };
+
+        if self.time.0 <= time_delta + 2 {
+            return;
+        }
+
+        round(
+            blueprint,
+            State {
+                time: Time(self.time.0 - time_delta),
+                obsidian_robots: Obsidian(self.obsidian_robots.0 + 1),
+                ore: Ore(self.ore.0 + time_delta * self.ore_robots.0 - blueprint.obsidian.0 .0),
+                clay: Clay(self.clay.0 + time_delta * self.clay_robots.0 - blueprint.obsidian.1 .0),
+                obsidian: Obsidian(self.obsidian.0 + time_delta * self.obsidian_robots.0),
+                ..self.clone()
+            },
+            max_geodes,
+        );
+    }
+
+    fn build_clay_robot(&self, blueprint: &Blueprint, max_geodes: &mut Geode) {
+        if self.clay_robots >= blueprint.clay_robot_limit {
+            return;
+        }
+
+        let is_building = self.ore >= blueprint.clay;
+        let time_delta = if is_building {
+            1
+        } else {
+            1 + div_ceil(blueprint.clay.0 - self.ore.0, self.ore_robots.0)
+        };
+
+        if self.time.0 <= time_delta + 3 {
+            return;
+        }
+
+        round(
+            blueprint,
+            State {
+                time: Time(self.time.0 - time_delta),
+                clay_robots: Clay(self.clay_robots.0 + 1),
+                ore: Ore(self.ore.0 + time_delta * self.ore_robots.0 - blueprint.clay.0),
+                clay: Clay(self.clay.0 + time_delta * self.clay_robots.0),
+                obsidian: Obsidian(self.obsidian.0 + time_delta * self.obsidian_robots.0),
+                ..self.clone()
+            },
+            max_geodes,
+        );
+    }
+
+    fn build_ore_robot(&self, blueprint: &Blueprint, max_geodes: &mut Geode) {
+        if self.ore_robots >= blueprint.ore_robot_limit {
+            return;
+        }
+
+        let is_building = self.ore >= blueprint.ore;
+        let time_delta = if is_building {
+            1
+        } else {
+            1 + div_ceil(blueprint.ore.0 - self.ore.0, self.ore_robots.0)
+        };
+
+        if self.time.0 <= time_delta + 4 {
+            return;
+        }
+
+        round(
+            blueprint,
+            State {
+                time: Time(self.time.0 - time_delta),
+                ore_robots: Ore(self.ore_robots.0 + 1),
+                ore: Ore(self.ore.0 + time_delta * self.ore_robots.0 - blueprint.ore.0),
+                clay: Clay(self.clay.0 + time_delta * self.clay_robots.0),
+                obsidian: Obsidian(self.obsidian.0 + time_delta * self.obsidian_robots.0),
+                ..self.clone()
+            },
+            max_geodes,
+        );
+    }
+}
+
+fn round(blueprint: &Blueprint, state: State, max_geodes: &mut Geode) {
+    if state.geodes > *max_geodes {
+        *max_geodes = state.geodes;
+    }
+
+    // Even a new geode robot every minute left can't beat the best so far
+    let t = state.time.0;
+
+    if state.geodes.0 + t * t.saturating_sub(1) / 2 <= max_geodes.0 {
+        return;
+    }
+
+    // Building a geode robot as soon as one is affordable isn't always best,
+    // so the other robots are tried as well
+    state.build_geode_robot(blueprint, max_geodes);
+    state.build_obsidian_robot(blueprint, max_geodes);
+    state.build_clay_robot(blueprint, max_geodes);
+    state.build_ore_robot(blueprint, max_geodes);
+}
+
+fn simulate(blueprint: &Blueprint, time: usize) -> usize {
+    let mut max_geodes = Geode(0);
+
+    round(
+        blueprint,
+        State {
+            time: Time(time),
+            ore_robots: Ore(1),
+            ..Default::default()
+        },
+        &mut max_geodes,
+    );
+
+    max_geodes.0
+}
+
+pub fn part_1(blueprints: &[Blueprint]) -> Result<usize, Error> {
+    if blueprints.is_empty() {
+        bail!("No blueprints");
+    }
+
+    Ok(blueprints
+        .iter()
+        .enumerate()
+        .map(|(i, blueprint)| (i, simulate(blueprint, 24)))
+        .map(|(i, max)| (i + 1) * max)
+        .sum())
+}
+
+pub fn part_2(blueprints: &[Blueprint]) -> Result<usize, Error> {
+    if blueprints.is_empty() {
+        bail!("No blueprints");
+    }
+
+    Ok(blueprints
+        .iter()
+        .take(3)
+        .fold(1, |acc, blueprint| acc * simulate(blueprint, 32)))
+}
+
+/// Bounded and non-zero so the search can't overflow or stall
+fn cost<'a>() -> impl Parser<'a, usize> {
+    int::<num::NonZeroU32>().map(|cost| cost.get() as usize)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Blueprint>, Error> {
+    let blueprint = (
+        prefixed("Blueprint", int::<usize>()).skip(tag(":")),
+        prefixed("Each ore robot costs", cost()).skip(tag("ore.")),
+        prefixed("Each clay robot costs", cost()).skip(tag("ore.")),
+        (
+            prefixed("Each obsidian robot costs", cost()),
+            prefixed("ore and", cost()).skip(tag("clay.")),
+        ),
+        (
+            prefixed("Each geode robot costs", cost()),
+            prefixed("ore and", cost()).skip(tag("obsidian.")),
+        ),
+    )
+        .map(|(id, ore, clay, obsidian, geode)| {
+            let mut blueprint = Blueprint {
+                id,
+                ore: Ore(ore),
+                clay: Ore(clay),
+                obsidian: (Ore(obsidian.0), Clay(obsidian.1)),
+                geode: (Ore(geode.0), Obsidian(geode.1)),
+                ..Default::default()
+            };
+
+            blueprint.clay_robot_limit = blueprint.obsidian.1;
+            blueprint.ore_robot_limit = blueprint
+                .ore
+                .max(blueprint.clay)
+                .max(blueprint.obsidian.0)
+                .max(blueprint.geode.0);
+
+            blueprint
+        });
+
+    Ok(parser::run(input, input, parser::many_to_end(blueprint))?)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 19;
+
+    type Input<'a> = Vec<Blueprint>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 30;
+
+    /// `size` blueprints with costs in the ranges real ones use
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let blueprints = (1..=size.max(1))
+            .map(|id| {
+                let [ore, clay, obsidian, geode] = [(); 4].map(|_| Ore(rng.below(3) + 2));
+
+                Blueprint {
+                    id,
+                    ore,
+                    clay,
+                    obsidian: (obsidian, Clay(rng.below(16) + 5)),
+                    geode: (geode, Obsidian(rng.below(14) + 7)),
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Joined(&blueprints, "\n").to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        Blueprint 1:
+            Each ore robot costs 4 ore.
+            Each clay robot costs 2 ore.
+            Each obsidian robot costs 3 ore and 14 clay.
+            Each geode robot costs 2 ore and 7 obsidian.
+
+        Blueprint 2:
+            Each ore robot costs 2 ore.
+            Each clay robot costs 3 ore.
+            Each obsidian robot costs 3 ore and 8 clay.
+            Each geode robot costs 3 ore and 12 obsidian.
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 33);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 3472);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() {
+        let input = INPUT.replace("3 ore and 8 clay", "3 ore and eight clay");
+        let err = super::parse(&input).unwrap_err();
+        let err = err.downcast_ref::<super::ParseError>();
+
+        assert_eq!(err.map(|e| (e.line, e.columns.clone())), Some((11, 49..54)));
+    }
+
+    #[test]
+    fn malformed() {
+        for input in [
+            String::new(),
+            INPUT.replace("costs 4 ore", "costs 0 ore"),
+            INPUT.replace("costs 4 ore", "costs 99999999999 ore"),
+            "Blueprint 1: Each ore robot costs 4 ore.".into(),
+        ] {
+            assert!(super::solve::<super::Day>(&input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let text = (1..=rng.below(5))
+                .map(|id| {
+                    let [a, b, c, d, e, g] = [(); 6].map(|_| rng.below(20) + 1);
+
+                    format!(
+                        "Blueprint {id}: Each ore robot costs {a} ore. \
+                         Each clay robot costs {b} ore. \
+                         Each obsidian robot costs {c} ore and {d} clay. \
+                         Each geode robot costs {e} ore and {g} obsidian.\n"
+                    )
+                })
+                .collect::<String>();
+            let input = super::parse(&text)?;
+            let output = super::Joined(&input, "\n").to_string();
+
+            super::prop::equal(&output, &text.trim_end().to_string())?;
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(2, 2);
+    }
+
+    // Building a geode robot as soon as possible gets only 21 geodes
+    #[test]
+    fn geode_robot_later() -> Result<(), super::Error> {
+        let input = super::parse(
+            "Blueprint 1: \
+             Each ore robot costs 2 ore. \
+             Each clay robot costs 1 ore. \
+             Each obsidian robot costs 1 ore and 2 clay. \
+             Each geode robot costs 3 ore and 1 obsidian.",
+        )?;
+
+        assert_eq!(super::simulate(&input[0], 14), 22);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+//! Slow but obviously correct solutions, trying every choice every minute
+
+use std::array;
+
+use util::*;
+
+use crate::Blueprint;
+
+/// Most geodes opened in `time` minutes
+pub fn geodes(blueprint: &Blueprint, time: usize) -> usize {
+    // Ore, clay, obsidian and geodes, then the robots collecting each
+    let costs = [
+        [blueprint.ore.0, 0, 0, 0],
+        [blueprint.clay.0, 0, 0, 0],
+        [blueprint.obsidian.0 .0, blueprint.obsidian.1 .0, 0, 0],
+        [blueprint.geode.0 .0, 0, blueprint.geode.1 .0, 0],
+    ];
+    let mut states = HashSet::from([([0; 4], [1, 0, 0, 0])]);
+
+    for minute in 1..=time {
+        let mut next = HashSet::new();
+
+        // Stock beyond what the remaining robots could ever cost is useless,
+        // and merging states that differ only there keeps the search small
+        let spendable: [usize; 3] = array::from_fn(|i| {
+            costs.iter().map(|cost| cost[i]).max().unwrap_or(0) * (time - minute)
+        });
+
+        for (stock, robots) in states {
+            let collected = |stock: [usize; 4]| {
+                array::from_fn(|i| match spendable.get(i) {
+                    Some(&max) => (stock[i] + robots[i]).min(max),
+                    None => stock[i] + robots[i],
+                })
+            };
+
+            next.insert((collected(stock), robots));
+
+            for (robot, cost) in costs.iter().enumerate() {
+                if (0..4).all(|i| stock[i] >= cost[i]) {
+                    let mut built = robots;
+
+                    built[robot] += 1;
+                    next.insert((collected(array::from_fn(|i| stock[i] - cost[i])), built));
+                }
+            }
+        }
+
+        states = next;
+    }
+
+    states
+        .into_iter()
+        .map(|(stock, _)| stock[3])
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test {
+    use util::differential::{check, disagree};
+
+    #[test]
+    fn differential() {
+        // Cheap robots, so that geodes open within the few minutes that can
+        // be searched exhaustively
+        check(
+            20,
+            |rng| {
+                let [a, b, c, d, e, f] = [(); 6].map(|_| rng.below(3) + 1);
+
+                format!(
+                    "Blueprint 1: Each ore robot costs {a} ore. \
+                     Each clay robot costs {b} ore. \
+                     Each obsidian robot costs {c} ore and {d} clay. \
+                     Each geode robot costs {e} ore and {f} obsidian."
+                )
+            },
+            |text| {
+                let blueprints = crate::parse(text).ok()?;
+
+                blueprints.iter().find_map(|blueprint| {
+                    disagree(
+                        &format!("blueprint {}", blueprint.id),
+                        Ok(crate::simulate(blueprint, 14)),
+                        Ok(super::geodes(blueprint, 14)),
+                    )
+                })
+            },
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! Day 20: Grove Positioning System
+
+use util::*;
+
+#[cfg(feature = "reference")]
+pub mod reference;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Num {
+    pub n: isize,
+    /// Position in the original list
+    pub tag: usize,
+}
+
+impl fmt::Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.n)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Num>, Error> {
+    input
+        .trim()
+        .lines()
+        .enumerate()
+        .map(|(tag, l)| {
+            let l = l.trim();
+
+            Ok(Num {
+                n: l.parse().at(input, l)?,
+                tag,
+            })
+        })
+        .collect()
+}
+
+fn mix(orig: &[Num], nums: &mut [Num]) -> Result<(), Error> {
+    // Moving within a single number (or none) never changes the order
+    if nums.len() < 2 {
+        return Ok(());
+    }
+
+    let len = nums.len() - 1;
+
+    for n in orig {
+        let i = nums
+            .iter()
+            .position(|v| v == n)
+            .context("Number missing from mix")?;
+
+        match n.n {
+            n if n > 0 => {
+                let d = (n as usize) % len;
+                let j = i + d;
+
+                if j < len {
+                    nums[i..=j].rotate_left(1);
+                } else {
+                    nums[(j % len)..=i].rotate_right(1);
+                }
+            }
+            n if n < 0 => {
+                let d = (n.unsigned_abs() % len) as isize;
+                let j = i as isize - d;
+
+                if j < 0 {
+                    nums[i..=((j + len as isize) as usize)].rotate_left(1);
+                } else {
+                    nums[(j as usize)..=i].rotate_right(1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn coordinates(nums: &[Num]) -> Result<isize, Error> {
+    let zero = nums.iter().position(|n| n.n == 0).context("Missing zero")?;
+    let get_n = |n: usize| nums[(zero + n) % nums.len()].n;
+
+    get_n(1000)
+        .checked_add(get_n(2000))
+        .and_then(|sum| sum.checked_add(get_n(3000)))
+        .context("Coordinates overflow")
+}
+
+pub fn part_1(orig: &[Num]) -> Result<isize, Error> {
+    let mut nums = orig.to_vec();
+
+    mix(orig, &mut nums)?;
+
+    coordinates(&nums)
+}
+
+pub fn part_2(orig: &[Num]) -> Result<isize, Error> {
+    let key = 811589153;
+    let orig = orig
+        .iter()
+        .map(|n| {
+            let n = n.n.checked_mul(key).map(|v| Num { n: v, ..*n });
+
+            n.context("Number too large to decrypt")
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut nums = orig.clone();
+
+    for _ in 0..10 {
+        mix(&orig, &mut nums)?;
+    }
+
+    coordinates(&nums)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    const DAY: u8 = 20;
+
+    type Input<'a> = Vec<Num>;
+    type Output1 = isize;
+    type Output2 = isize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+        part_2(input)
+    }
+}
+
+impl Generate for Day {
+    const SIZE: usize = 5000;
+
+    /// `size` numbers, exactly one of them zero
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut nums = (1..size.max(1))
+            .map(|_| match rng.range(-10000..=9999) {
+                n if n >= 0 => n + 1,
+                n => n,
+            })
+            .collect::<Vec<_>>();
+
+        nums.insert(rng.below(nums.len() + 1), 0);
+
+        Joined(&nums, "\n").to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    static INPUT: &str = r#"
+        1
+        2
+        -3
+        3
+        -2
+        0
+        4
+    "#;
+
+    #[test]
+    fn part_1() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_1(&input)?, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_2() -> Result<(), super::Error> {
+        let input = super::parse(INPUT)?;
+
+        assert_eq!(super::part_2(&input)?, 1623178306);
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed() {
+        for input in ["", "x", "1\n2"] {
+            assert!(super::solve::<super::Day>(input).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        super::prop::check(|rng| {
+            let text = (0..rng.below(20))
+                .map(|_| format!("{}\n", rng.range(-9999..=9999)))
+                .collect::<String>();
+            let input = super::parse(&text)?;
+            let output = super::Joined(&input, "\n").to_string();
+
+            super::prop::equal(&output, &text.trim_end().to_string())?;
+            super::prop::equal(&super::parse(&output)?, &input)
+        });
+    }
+
+    #[test]
+    fn generated() {
+        super::generate::check::<super::Day>(200, 10);
+    }
+}
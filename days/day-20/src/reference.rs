@@ -0,0 +1,68 @@
+//! Slow but obviously correct solutions, moving numbers one step at a time
+
+use util::*;
+
+use crate::Num;
+
+fn decrypt(orig: &[Num], key: isize, rounds: usize) -> Result<isize, Error> {
+    let len = orig.len();
+    let mut nums = orig.to_vec();
+
+    for _ in 0..rounds {
+        for n in orig {
+            let mut i = nums.iter().position(|m| m == n).context("Number missing")?;
+
+            // A whole lap past the other numbers leaves the circle as it was,
+            // and so does going the other way round
+            let laps = len.saturating_sub(1).max(1) as i128;
+            let steps = (n.n as i128 * key as i128).rem_euclid(laps);
+
+            for _ in 0..steps {
+                let j = (i + 1) % len;
+
+                nums.swap(i, j);
+                i = j;
+            }
+        }
+    }
+
+    let zero = nums.iter().position(|n| n.n == 0).context("Missing zero")?;
+    let sum = [1000, 2000, 3000]
+        .into_iter()
+        .map(|k| nums[(zero + k) % len].n as i128 * key as i128)
+        .sum::<i128>();
+
+    isize::try_from(sum).context("Coordinates overflow")
+}
+
+pub fn part_1(orig: &[Num]) -> Result<isize, Error> {
+    decrypt(orig, 1, 1)
+}
+
+pub fn part_2(orig: &[Num]) -> Result<isize, Error> {
+    decrypt(orig, 811589153, 10)
+}
+
+#[cfg(test)]
+mod test {
+    use util::differential::{check, disagree};
+    use util::Generate;
+
+    #[test]
+    fn differential() {
+        check(
+            100,
+            |rng| {
+                let size = rng.below(30) + 1;
+
+                <crate::Day as Generate>::generate(rng, size)
+            },
+            |text| {
+                let input = crate::parse(text).ok()?;
+
+                disagree("part 1", crate::part_1(&input), super::part_1(&input))
+                    .or_else(|| disagree("part 2", crate::part_2(&input), super::part_2(&input)))
+            },
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use std::time::{Duration, Instant};
+
+use util::*;
+
+/// Bounds on the work a fuzz input may ask of a solver
+///
+/// Parsing must cope with anything, but large valid inputs are slow rather
+/// than wrong, so the parts only run on inputs within the step budget. Those
+/// must then finish within the time budget or the input is reported.
+#[derive(Clone, Copy, Debug)]
+pub struct Budget {
+    pub max_len: usize,
+    pub max_lines: usize,
+    /// Largest number anywhere in the input
+    pub max_number: u64,
+    pub time: Duration,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self {
+            max_len: 4096,
+            max_lines: 200,
+            max_number: 10_000,
+            // Generous, since fuzzing builds are instrumented
+            time: Duration::from_secs(10),
+        }
+    }
+}
+
+impl Budget {
+    /// Budget for a day, tightened where the work grows quickly with the input
+    pub fn for_day(day: u8) -> Self {
+        let default = Self::default();
+
+        match day {
+            // Sand fills an area quadratic in the depth of the cave
+            14 => Self {
+                max_number: 200,
+                ..default
+            },
+            // Part 2 walks the perimeter of every sensor against every other
+            15 => Self {
+                max_lines: 30,
+                ..default
+            },
+            // Valve search is exponential in the number of valves
+            16 => Self {
+                max_lines: 12,
+                ..default
+            },
+            // The flood fill covers the droplet's bounding box
+            18 => Self {
+                max_number: 50,
+                ..default
+            },
+            19 => Self {
+                max_lines: 3,
+                ..default
+            },
+            _ => default,
+        }
+    }
+
+    pub fn allows(&self, text: &str) -> bool {
+        text.len() <= self.max_len
+            && text.lines().count() <= self.max_lines
+            && text
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|n| !n.is_empty())
+                .all(|n| n.parse().is_ok_and(|n: u64| n <= self.max_number))
+    }
+}
+
+/// Feed arbitrary bytes to a day's parser, then to both parts within budget
+pub fn run<S: Solution>(data: &[u8]) {
+    let text = String::from_utf8_lossy(data);
+    let budget = Budget::for_day(S::DAY);
+
+    let Ok(input) = S::parse(&text) else {
+        return;
+    };
+
+    if !budget.allows(&text) {
+        return;
+    }
+
+    let start = Instant::now();
+    let _ = S::part_1(&input);
+    let _ = S::part_2(&input);
+    let elapsed = start.elapsed();
+
+    assert!(
+        elapsed <= budget.time,
+        "Day {} took {elapsed:?}, over its {:?} budget",
+        S::DAY,
+        budget.time
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path};
+
+    use super::*;
+
+    macro_rules! replay {
+        ($($day:ident),*) => {
+            $(
+                let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("corpus")
+                    .join(stringify!($day));
+
+                for path in input::files(&dir)? {
+                    run::<$day::Day>(&fs::read(path)?);
+                }
+            )*
+        };
+    }
+
+    #[test]
+    fn budget() {
+        let budget = Budget::default();
+
+        assert!(budget.allows("1-2,3-4\n"));
+        assert!(!budget.allows("10001"));
+        assert!(!budget.allows("99999999999999999999999"));
+        assert!(!Budget::for_day(16).allows(&"\n".repeat(13)));
+    }
+
+    // Seeds must run cleanly, so regressions show up without a fuzzer
+    #[test]
+    fn corpus() -> Result<(), Error> {
+        replay!(
+            day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11,
+            day_12, day_13, day_14, day_15, day_16, day_17, day_18, day_19, day_20
+        );
+
+        Ok(())
+    }
+}
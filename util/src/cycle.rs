@@ -0,0 +1,137 @@
+use std::hash::Hash;
+
+use crate::*;
+
+/// Repeating stretch of a sequence of states
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    /// Steps before the cycle is first entered
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Extrapolate a metric that grows by the same amount every period
+    ///
+    /// `values[i]` is the metric after `i` steps, and must cover at least
+    /// `start + period` steps. Returns `None` if it doesn't, or on overflow.
+    pub fn extrapolate(&self, values: &[usize], steps: usize) -> Option<usize> {
+        if let Some(&value) = values.get(steps) {
+            return Some(value);
+        }
+
+        let first = *values.get(self.start)?;
+        let gain = values.get(self.start + self.period)?.checked_sub(first)?;
+        let cycles = (steps - self.start) / self.period;
+        let rest = (steps - self.start) % self.period;
+
+        values[self.start + rest].checked_add(gain.checked_mul(cycles)?)
+    }
+
+    /// Whether a metric grows by the same amount at every step of the period
+    /// after the first, which a cycle found from approximate states may not
+    ///
+    /// `values` must cover at least `start + 2 * period` steps.
+    pub fn confirm(&self, values: &[usize]) -> bool {
+        let (start, period) = (self.start, self.period);
+
+        values.len() > start + 2 * period
+            && (0..=period).all(|i| {
+                values[start + period + i].checked_sub(values[start + i])
+                    == values[start + period].checked_sub(values[start])
+            })
+    }
+}
+
+/// Finds cycles by remembering when each state was first seen
+#[derive(Clone, Debug)]
+pub struct Detector<S> {
+    seen: HashMap<S, usize>,
+}
+
+impl<S: Eq + Hash> Detector<S> {
+    pub fn new() -> Self {
+        Self {
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Record the state after the next step, returning the cycle once a
+    /// state repeats
+    pub fn push(&mut self, state: S) -> Option<Cycle> {
+        let step = self.seen.len();
+
+        match self.seen.entry(state) {
+            Entry::Occupied(e) => Some(Cycle {
+                start: *e.get(),
+                period: step - e.get(),
+            }),
+            Entry::Vacant(e) => {
+                e.insert(step);
+                None
+            }
+        }
+    }
+}
+
+impl<S: Eq + Hash> Default for Detector<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Find the first cycle in a sequence of states, starting from step 0
+pub fn find<S: Eq + Hash>(states: impl IntoIterator<Item = S>) -> Option<Cycle> {
+    let mut detector = Detector::new();
+
+    states.into_iter().find_map(|state| detector.push(state))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn find() {
+        // 0, 1, 2, 3, 4, 2, 3, 4, ...
+        let states = (0..).map(|i| if i < 2 { i } else { 2 + (i - 2) % 3 });
+
+        assert_eq!(
+            super::find(states),
+            Some(Cycle {
+                start: 2,
+                period: 3
+            })
+        );
+        assert_eq!(super::find(0..10), None);
+    }
+
+    #[test]
+    fn extrapolate() {
+        let cycle = Cycle {
+            start: 2,
+            period: 3,
+        };
+        // Gains 10 over every period from step 2 onwards
+        let values = [0, 1, 2, 5, 6, 12];
+
+        assert_eq!(cycle.extrapolate(&values, 4), Some(6));
+        assert_eq!(cycle.extrapolate(&values, 8), Some(22));
+        assert_eq!(cycle.extrapolate(&values, 9), Some(25));
+        assert_eq!(cycle.extrapolate(&values[..4], 9), None);
+        assert_eq!(cycle.extrapolate(&values, usize::MAX), None);
+    }
+
+    #[test]
+    fn confirm() {
+        let cycle = Cycle {
+            start: 2,
+            period: 3,
+        };
+
+        assert!(cycle.confirm(&[0, 1, 2, 5, 6, 12, 15, 16, 22]));
+        // Same gain over the second period, but not step for step
+        assert!(!cycle.confirm(&[0, 1, 2, 5, 6, 12, 14, 17, 22]));
+        assert!(!cycle.confirm(&[0, 1, 2, 5, 6, 12, 15, 16]));
+    }
+}
@@ -0,0 +1,123 @@
+//! Differential testing of solvers against slow reference solutions
+
+use crate::rng::Rng;
+use crate::*;
+
+/// Describe how a solver's result differs from the reference's, if it does
+///
+/// Inputs the reference rejects break the puzzle's guarantees, so whatever
+/// the solver makes of them counts as agreeing.
+pub fn disagree<T: fmt::Debug + PartialEq>(
+    what: &str,
+    actual: Result<T, Error>,
+    expected: Result<T, Error>,
+) -> Option<String> {
+    match (actual, expected) {
+        (Ok(a), Ok(e)) if a != e => Some(format!("{what}: expected {e:?} but got {a:?}")),
+        (Err(err), Ok(e)) => Some(format!("{what}: expected {e:?} but failed: {err}")),
+        _ => None,
+    }
+}
+
+/// Smallest part of `text` that still `fails`, dropping lines or, for a
+/// single line, words or else characters
+pub fn minimize(text: &str, fails: impl Fn(&str) -> bool) -> String {
+    let text = text.trim();
+    let (mut units, sep) = if text.contains('\n') {
+        (text.lines().collect::<Vec<_>>(), "\n")
+    } else if text.contains(char::is_whitespace) {
+        (text.split_whitespace().collect(), " ")
+    } else {
+        let chars = text.char_indices();
+
+        (chars.map(|(i, c)| &text[i..i + c.len_utf8()]).collect(), "")
+    };
+    let mut chunk = (units.len() / 2).max(1);
+
+    loop {
+        let mut removed = false;
+        let mut i = 0;
+
+        while i < units.len() {
+            let rest = [&units[..i], &units[(i + chunk).min(units.len())..]].concat();
+
+            if !rest.is_empty() && fails(&rest.join(sep)) {
+                units = rest;
+                removed = true;
+            } else {
+                i += chunk;
+            }
+        }
+
+        if chunk > 1 {
+            chunk /= 2;
+        } else if !removed {
+            break units.join(sep);
+        }
+    }
+}
+
+/// Compare a solver with its reference on inputs generated from `cases`
+/// seeds, using `compare` to describe any disagreement
+///
+/// Panics with the first seed the two disagree on and the input minimized
+/// as far as they keep disagreeing.
+pub fn check(
+    cases: u64,
+    mut generate: impl FnMut(&mut Rng) -> String,
+    compare: impl Fn(&str) -> Option<String>,
+) {
+    for seed in 0..cases {
+        let text = generate(&mut Rng::new(seed));
+
+        if compare(&text).is_some() {
+            let text = minimize(&text, |text| compare(text).is_some());
+            let diff = compare(&text).unwrap_or_default();
+
+            panic!("Failed with seed {seed}: {diff}\n\nMinimized input:\n{text}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn disagree() {
+        let fail = || Err(anyhow::anyhow!("bad"));
+
+        assert_eq!(super::disagree("a", Ok(1), Ok(1)), None);
+        assert_eq!(super::disagree("a", Ok(1), fail()), None);
+        assert_eq!(
+            super::disagree("a", Ok(1), Ok(2)).as_deref(),
+            Some("a: expected 2 but got 1")
+        );
+        assert!(super::disagree("a", fail(), Ok(2)).is_some());
+    }
+
+    #[test]
+    fn minimize() {
+        let fails = |text: &str| text.contains('3') && text.contains('7');
+
+        assert_eq!(super::minimize("1\n2\n3\n4\n5\n6\n7\n8", fails), "3\n7");
+        assert_eq!(super::minimize("1 3  5 7", fails), "3 7");
+        assert_eq!(super::minimize("<<3>><7<", fails), "37");
+    }
+
+    #[test]
+    #[should_panic(expected = "Minimized input:\n7")]
+    fn check() {
+        super::check(
+            10,
+            |rng| {
+                let lines = (0..20).map(|_| rng.below(10).to_string());
+
+                lines.collect::<Vec<_>>().join("\n")
+            },
+            |text| {
+                text.lines()
+                    .any(|l| l == "7")
+                    .then(|| "Found 7".to_string())
+            },
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::*;
+
+/// Parse failure pointing at the offending part of the input
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Character columns on the line, starting at 1
+    pub columns: ops::Range<usize>,
+    /// Full text of the offending line
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Create an error for `span`, which should be a slice of `input`
+    ///
+    /// Spans that were not sliced from `input` are located by searching
+    /// for their text and fall back to the start of the input.
+    pub fn new(input: &str, span: &str, message: impl fmt::Display) -> Self {
+        let offset = (span.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + span.len() <= input.len())
+            .or_else(|| input.find(span))
+            .unwrap_or(0);
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        // Spans crossing lines are clipped to the first one
+        let span_end = (offset + span.len()).min(line_end);
+        let start = input[line_start..offset].chars().count() + 1;
+        let len = input[offset..span_end].chars().count().max(1);
+
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            columns: start..(start + len),
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let gutter = self.line.to_string().len();
+
+        writeln!(
+            f,
+            "{} (line {}, column {})",
+            self.message, self.line, self.columns.start
+        )?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{:gutter$} | {}{}",
+            "",
+            " ".repeat(self.columns.start - 1),
+            "^".repeat(self.columns.len())
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Attach the location of a failure to a [`ParseError`]
+pub trait Located<T> {
+    /// Report a failure at `span`, a slice of the full `input`
+    fn at(self, input: &str, span: &str) -> Result<T, ParseError>;
+}
+
+impl<T, E: fmt::Display> Located<T> for Result<T, E> {
+    fn at(self, input: &str, span: &str) -> Result<T, ParseError> {
+        self.map_err(|err| ParseError::new(input, span, err))
+    }
+}
+
+/// Empty span at the end of `input`, for input that ends too early
+pub fn end(input: &str) -> &str {
+    &input[input.len()..]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "498,4 -> 498,6\n503,4 -> 50x,4\n";
+
+    #[test]
+    fn locate() {
+        let line = INPUT.lines().nth(1).unwrap_or_default();
+        let err = ParseError::new(INPUT, &line[9..12], "invalid digit");
+
+        assert_eq!(err.line, 2);
+        assert_eq!(err.columns, 10..13);
+        assert_eq!(err.text, "503,4 -> 50x,4");
+        assert_eq!(
+            err.to_string(),
+            "invalid digit (line 2, column 10)\n  |\n2 | 503,4 -> 50x,4\n  |          ^^^"
+        );
+    }
+
+    #[test]
+    fn located() {
+        let span = &INPUT[9..14];
+        let err = span.parse::<usize>().at(INPUT, span).unwrap_err();
+
+        assert_eq!((err.line, err.columns.clone()), (1, 10..15));
+        assert_eq!(err.message, "invalid digit found in string");
+    }
+
+    #[test]
+    fn end_of_input() {
+        let err = ParseError::new(INPUT, end(INPUT), "missing moves");
+
+        assert_eq!((err.line, err.columns), (3, 1..2));
+        assert_eq!(err.text, "");
+    }
+
+    #[test]
+    fn unrelated_span() {
+        let err = ParseError::new(INPUT, "503", "missing");
+
+        assert_eq!((err.line, err.columns), (2, 1..4));
+    }
+}
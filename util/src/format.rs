@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::*;
+
+/// Output format for solved days
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    /// Human readable `Part 1: ...` lines
+    #[default]
+    Text,
+    /// One JSON record per part, each on its own line
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "text" => Format::Text,
+            "json" => Format::Json,
+            _ => bail!("Unexpected format: {s}"),
+        })
+    }
+}
+
+/// Machine-readable answer for a single part
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub answer: &'a str,
+    pub parse_ms: f64,
+    pub solve_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorRecord<'a> {
+    day: u8,
+    error: &'a str,
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+impl Report {
+    pub fn records(&self) -> [Record<'_>; 2] {
+        let parse_ms = millis(self.timings.parse);
+
+        [
+            Record {
+                day: self.day,
+                part: 1,
+                answer: &self.answers.part_1,
+                parse_ms,
+                solve_ms: millis(self.timings.part_1),
+            },
+            Record {
+                day: self.day,
+                part: 2,
+                answer: &self.answers.part_2,
+                parse_ms,
+                solve_ms: millis(self.timings.part_2),
+            },
+        ]
+    }
+}
+
+impl Format {
+    pub fn render(self, report: &Report) -> Result<String, Error> {
+        Ok(match self {
+            Format::Text => report.answers.to_string(),
+            Format::Json => report
+                .records()
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<Result<Vec<_>, _>>()?
+                .join("\n"),
+        })
+    }
+
+    pub fn render_error(self, day: u8, err: &Error) -> Result<String, Error> {
+        Ok(match self {
+            Format::Text => format!("Error: {err:#}"),
+            Format::Json => serde_json::to_string(&ErrorRecord {
+                day,
+                error: &format!("{err:#}"),
+            })?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn json() -> Result<(), Error> {
+        let report = Report {
+            day: 10,
+            answers: Answers {
+                part_1: "13140".into(),
+                part_2: "##..\n..##".into(),
+            },
+            timings: Timings {
+                parse: Duration::from_millis(1),
+                part_1: Duration::from_millis(2),
+                part_2: Duration::from_millis(3),
+            },
+        };
+
+        let json = Format::Json.render(&report)?;
+        let lines = json.lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            [
+                r###"{"day":10,"part":1,"answer":"13140","parse_ms":1.0,"solve_ms":2.0}"###,
+                r###"{"day":10,"part":2,"answer":"##..\n..##","parse_ms":1.0,"solve_ms":3.0}"###,
+            ]
+        );
+
+        Ok(())
+    }
+}
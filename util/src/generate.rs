@@ -0,0 +1,22 @@
+use crate::*;
+
+/// Random but well-formed puzzle inputs, for stress tests and benchmarks
+///
+/// Generated inputs keep the guarantees the puzzles make about real ones,
+/// so both parts must solve them.
+pub trait Generate: Solution {
+    /// Size of a typical personal input
+    const SIZE: usize;
+
+    /// Input text of about `size` records, e.g. lines, monkeys or valves
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+/// Check that `S` solves inputs of `size` generated from `cases` seeds
+pub fn check<S: Generate>(size: usize, cases: u64) {
+    prop::check_cases(cases, |rng| {
+        let input = S::generate(rng, size);
+
+        solve::<S>(&input).map(|_| ()).context(input)
+    });
+}
@@ -0,0 +1,287 @@
+use std::array;
+
+use crate::*;
+
+/// Position or offset on a 2D plane, with `y` growing downwards
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// Position or offset in 3D space
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+macro_rules! point {
+    ($point:ident { $($axis:ident),+ }) => {
+        impl $point {
+            pub const ORIGIN: Self = Self { $($axis: 0),+ };
+
+            pub const fn new($($axis: isize),+) -> Self {
+                Self { $($axis),+ }
+            }
+
+            /// Sum of the distances along each axis
+            pub fn manhattan(self, other: Self) -> usize {
+                0 $(+ self.$axis.abs_diff(other.$axis))+
+            }
+
+            /// Largest distance along any axis
+            pub fn chebyshev(self, other: Self) -> usize {
+                0 $(.max(self.$axis.abs_diff(other.$axis)))+
+            }
+
+            /// Step of at most one along each axis in the same direction
+            pub fn signum(self) -> Self {
+                Self { $($axis: self.$axis.signum()),+ }
+            }
+
+            /// Whether this lies in the box from `min` to `max` inclusive
+            pub fn within(self, min: Self, max: Self) -> bool {
+                true $(&& (min.$axis..=max.$axis).contains(&self.$axis))+
+            }
+
+            pub fn checked_add(self, other: Self) -> Option<Self> {
+                Some(Self { $($axis: self.$axis.checked_add(other.$axis)?),+ })
+            }
+
+            /// Inclusive corners of the smallest box containing all `points`
+            pub fn bounds(points: impl IntoIterator<Item = Self>) -> Option<(Self, Self)> {
+                points.into_iter().fold(None, |bounds, p| {
+                    let (min, max) = bounds.unwrap_or((p, p));
+
+                    Some((
+                        Self { $($axis: min.$axis.min(p.$axis)),+ },
+                        Self { $($axis: max.$axis.max(p.$axis)),+ },
+                    ))
+                })
+            }
+        }
+
+        impl ops::Add for $point {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($axis: self.$axis + other.$axis),+ }
+            }
+        }
+
+        impl ops::Sub for $point {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($axis: self.$axis - other.$axis),+ }
+            }
+        }
+
+        impl ops::Mul<isize> for $point {
+            type Output = Self;
+
+            fn mul(self, n: isize) -> Self {
+                Self { $($axis: self.$axis * n),+ }
+            }
+        }
+
+        impl ops::Neg for $point {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($axis: -self.$axis),+ }
+            }
+        }
+
+        impl ops::AddAssign for $point {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl ops::SubAssign for $point {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+point!(Point2 { x, y });
+point!(Point3 { x, y, z });
+
+impl Point2 {
+    /// Orthogonal neighbours, clockwise from up
+    pub fn neighbors4(self) -> [Self; 4] {
+        Direction::ALL.map(|d| self + d.step())
+    }
+
+    /// Orthogonal and diagonal neighbours, clockwise from up-left
+    pub fn neighbors8(self) -> [Self; 8] {
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+        ]
+        .map(|(x, y)| self + Self::new(x, y))
+    }
+}
+
+impl Point3 {
+    /// Neighbours sharing a face
+    pub fn neighbors6(self) -> [Self; 6] {
+        [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ]
+        .map(|(x, y, z)| self + Self::new(x, y, z))
+    }
+
+    /// Neighbours sharing a face, edge or corner
+    pub fn neighbors26(self) -> [Self; 26] {
+        array::from_fn(|i| {
+            // Skip the centre of the 3x3x3 cube
+            let i = if i < 13 { i } else { i + 1 } as isize;
+
+            self + Self::new(i % 3 - 1, i / 3 % 3 - 1, i / 9 - 1)
+        })
+    }
+}
+
+impl From<(isize, isize)> for Point2 {
+    fn from((x, y): (isize, isize)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<(isize, isize, isize)> for Point3 {
+    fn from((x, y, z): (isize, isize, isize)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl fmt::Display for Point2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// Orthogonal direction on a plane with `y` growing downwards
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from up
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Offset of one step in this direction
+    pub fn step(self) -> Point2 {
+        match self {
+            Self::Up => Point2::new(0, -1),
+            Self::Right => Point2::new(1, 0),
+            Self::Down => Point2::new(0, 1),
+            Self::Left => Point2::new(-1, 0),
+        }
+    }
+
+    /// Quarter turn clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Quarter turn anticlockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(-3, 4);
+
+        assert_eq!(a + b, Point2::new(-2, 2));
+        assert_eq!(a - b, Point2::new(4, -6));
+        assert_eq!(-a * 2, Point2::new(-2, 4));
+        assert_eq!((b - a).signum(), Point2::new(-1, 1));
+        assert_eq!(a.checked_add(Point2::new(isize::MAX, 0)), None);
+        assert_eq!(a.to_string(), "1,-2");
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(-1, 5, 3);
+
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(a.chebyshev(b), 3);
+        assert_eq!(Point2::ORIGIN.manhattan(Point2::new(-2, 2)), 4);
+    }
+
+    #[test]
+    fn neighbors() {
+        let p = Point3::new(0, 0, 0);
+        let all = p.neighbors26();
+
+        assert!(!all.contains(&p));
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 26);
+        assert!(all.iter().all(|n| n.chebyshev(p) == 1));
+        assert!(p.neighbors6().iter().all(|n| n.manhattan(p) == 1));
+        assert!(Point2::ORIGIN
+            .neighbors8()
+            .iter()
+            .all(|n| n.chebyshev(Point2::ORIGIN) == 1));
+        assert_eq!(Point2::new(1, 1).neighbors4()[0], Point2::new(1, 0));
+    }
+
+    #[test]
+    fn bounds() {
+        let points = [Point2::new(3, -1), Point2::new(-2, 4), Point2::new(0, 0)];
+
+        assert_eq!(
+            Point2::bounds(points),
+            Some((Point2::new(-2, -1), Point2::new(3, 4)))
+        );
+        assert_eq!(Point3::bounds([]), None);
+        assert!(Point2::ORIGIN.within(Point2::new(-2, -1), Point2::new(3, 4)));
+        assert!(!Point2::new(4, 0).within(Point2::new(-2, -1), Point2::new(3, 4)));
+    }
+
+    #[test]
+    fn direction() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert_eq!(Direction::Down.step(), -Direction::Up.step());
+    }
+}
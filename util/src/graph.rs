@@ -0,0 +1,218 @@
+use std::hash::Hash;
+
+use crate::*;
+
+/// Shortest paths found by a search, from its start to every node reached
+#[derive(Clone, Debug)]
+pub struct Paths<N, C> {
+    pub dist: HashMap<N, C>,
+    /// Node each node was first reached from on a shortest path
+    pub prev: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C> Paths<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Self {
+            dist: HashMap::from([(start, zero)]),
+            prev: HashMap::new(),
+        }
+    }
+
+    /// Nodes on the path from the start to `to`, inclusive
+    pub fn path(&self, to: &N) -> Option<Vec<N>> {
+        if !self.dist.contains_key(to) {
+            return None;
+        }
+
+        let mut path =
+            iter::successors(Some(to.clone()), |n| self.prev.get(n).cloned()).collect::<Vec<_>>();
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth first search over unit cost edges
+pub fn bfs<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new(start.clone(), 0);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, cost)) = queue.pop_front() {
+        for next in neighbors(&node) {
+            if let Entry::Vacant(e) = paths.dist.entry(next.clone()) {
+                e.insert(cost + 1);
+                paths.prev.insert(next.clone(), node.clone());
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    paths
+}
+
+/// Dijkstra's algorithm over edges with non-negative costs
+pub fn dijkstra<N, C, I>(start: N, neighbors: impl FnMut(&N) -> I) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Default + Ord + ops::Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    search(start, None, neighbors, |_| C::default())
+}
+
+/// A* search from `start` until `goal` is reached
+///
+/// `heuristic` must never overestimate the remaining cost to `goal`, or the
+/// path found may not be the shortest.
+pub fn astar<N, C, I>(
+    start: N,
+    goal: &N,
+    neighbors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Default + Ord + ops::Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    search(start, Some(goal), neighbors, heuristic)
+}
+
+/// Node waiting in the frontier, ordered so the heap pops the lowest estimate
+struct Visit<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Visit<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Visit<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Visit<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<N, C: Ord> Eq for Visit<N, C> {}
+
+fn search<N, C, I>(
+    start: N,
+    goal: Option<&N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Default + Ord + ops::Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new(start.clone(), C::default());
+    let mut heap = BinaryHeap::from([Visit {
+        estimate: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    while let Some(Visit { cost, node, .. }) = heap.pop() {
+        if goal == Some(&node) {
+            break;
+        }
+
+        if paths.dist.get(&node).is_some_and(|&n| cost > n) {
+            continue;
+        }
+
+        for (next, step) in neighbors(&node) {
+            let cost = cost + step;
+
+            if paths.dist.get(&next).is_none_or(|&n| cost < n) {
+                paths.dist.insert(next.clone(), cost);
+                paths.prev.insert(next.clone(), node.clone());
+                heap.push(Visit {
+                    estimate: cost + heuristic(&next),
+                    cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    //  0 -1- 1 -1- 2
+    //  |           |
+    //  5     3 -1- 4
+    //  |           |
+    //  +-----1-----+
+    fn edges(n: &u8) -> Vec<(u8, u32)> {
+        match n {
+            0 => vec![(1, 1), (4, 5)],
+            1 => vec![(0, 1), (2, 1)],
+            2 => vec![(1, 1), (4, 1)],
+            3 => vec![(4, 1)],
+            4 => vec![(2, 1), (3, 1), (0, 5)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs() {
+        let paths = super::bfs(0, |n| edges(n).into_iter().map(|(n, _)| n));
+
+        assert_eq!(paths.dist.get(&3), Some(&2));
+        assert_eq!(paths.path(&3), Some(vec![0, 4, 3]));
+        assert_eq!(paths.path(&9), None);
+    }
+
+    #[test]
+    fn dijkstra() {
+        let paths = super::dijkstra(0, edges);
+
+        assert_eq!(paths.dist.get(&4), Some(&3));
+        assert_eq!(paths.path(&3), Some(vec![0, 1, 2, 4, 3]));
+        assert_eq!(paths.path(&0), Some(vec![0]));
+    }
+
+    #[test]
+    fn astar() {
+        let paths = super::astar(0, &4, edges, |&n| u32::from(4u8.abs_diff(n)) / 2);
+
+        assert_eq!(paths.dist.get(&4), Some(&3));
+        assert_eq!(paths.path(&4), Some(vec![0, 1, 2, 4]));
+    }
+
+    #[test]
+    fn grid() -> Result<(), super::Error> {
+        let grid = Grid::parse("..#\n#..\n...", |c| Ok::<_, Error>(c == '#'))?;
+        let goal = Point2::new(0, 2);
+        let paths = super::astar(
+            Point2::ORIGIN,
+            &goal,
+            |&p| grid.neighbors4(p).filter(|&p| !grid[p]).map(|p| (p, 1)),
+            |p| p.manhattan(goal),
+        );
+
+        assert_eq!(paths.dist.get(&goal), Some(&4));
+
+        Ok(())
+    }
+}
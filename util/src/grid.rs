@@ -0,0 +1,303 @@
+use crate::*;
+
+/// Dense, bounds-checked 2D grid
+///
+/// Cells are stored row by row starting at `origin`, so positions may be
+/// negative. Lookups outside the grid return `None` rather than panicking.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    origin: Point2,
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parse a character map, one row per line
+    ///
+    /// Lines are trimmed and must all be the same width. Errors from `f` are
+    /// reported at the offending character.
+    pub fn parse<E: fmt::Display>(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for line in input.trim().lines().map(str::trim) {
+            let len = line.chars().count();
+
+            if height == 0 {
+                width = len;
+            } else if len != width {
+                return Err(ParseError::new(
+                    input,
+                    line,
+                    format!("Row {} has {len} cells, expected {width}", height + 1),
+                ));
+            }
+
+            for (i, c) in line.char_indices() {
+                cells.push(f(c).at(input, &line[i..(i + c.len_utf8())])?);
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            origin: Point2::ORIGIN,
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Smallest and largest positions in the grid, inclusive
+    pub fn bounds(&self) -> (Point2, Point2) {
+        let size = Point2::new(self.width as isize, self.height as isize);
+
+        (self.origin, self.origin + size - Point2::new(1, 1))
+    }
+
+    fn index(&self, pos: Point2) -> Option<usize> {
+        let x = usize::try_from(pos.x.checked_sub(self.origin.x)?).ok()?;
+        let y = usize::try_from(pos.y.checked_sub(self.origin.y)?).ok()?;
+
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    fn pos(&self, i: usize) -> Point2 {
+        self.origin + Point2::new((i % self.width) as isize, (i / self.width) as isize)
+    }
+
+    pub fn contains(&self, pos: Point2) -> bool {
+        self.index(pos).is_some()
+    }
+
+    pub fn get(&self, pos: Point2) -> Option<&T> {
+        self.index(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Point2) -> Option<&mut T> {
+        self.index(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// All cells along with their positions, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> + '_ {
+        self.cells.iter().enumerate().map(|(i, c)| (self.pos(i), c))
+    }
+
+    /// All positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Point2> + '_ {
+        (0..self.cells.len()).map(|i| self.pos(i))
+    }
+
+    /// Cells of row `y`, if it is within the grid
+    pub fn row(&self, y: isize) -> Option<&[T]> {
+        let i = self.index(Point2::new(self.origin.x, y))?;
+
+        Some(&self.cells[i..(i + self.width)])
+    }
+
+    /// Cells of column `x`, top to bottom
+    pub fn column(&self, x: isize) -> impl Iterator<Item = &T> + '_ {
+        let (min, max) = self.bounds();
+
+        (min.y..=max.y).filter_map(move |y| self.get(Point2::new(x, y)))
+    }
+
+    /// Cells from `pos` in steps of `step` until the edge, excluding `pos`
+    pub fn ray(&self, pos: Point2, step: Point2) -> impl Iterator<Item = (Point2, &T)> + '_ {
+        iter::successors(Some(pos), move |p| p.checked_add(step))
+            .skip(1)
+            .map_while(|p| Some((p, self.get(p)?)))
+    }
+
+    /// Orthogonal neighbours of `pos` within the grid
+    pub fn neighbors4(&self, pos: Point2) -> impl Iterator<Item = Point2> + '_ {
+        pos.neighbors4().into_iter().filter(|&p| self.contains(p))
+    }
+
+    /// Orthogonal and diagonal neighbours of `pos` within the grid
+    pub fn neighbors8(&self, pos: Point2) -> impl Iterator<Item = Point2> + '_ {
+        pos.neighbors8().into_iter().filter(|&p| self.contains(p))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Grid of `width` by `height` cells starting at `(0, 0)`
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            origin: Point2::ORIGIN,
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Grid covering `min` to `max` inclusive
+    pub fn with_bounds(min: Point2, max: Point2, fill: T) -> Self {
+        let size = |min: isize, max: isize| usize::try_from(max - min + 1).unwrap_or(0);
+
+        Self {
+            origin: min,
+            ..Self::new(size(min.x, max.x), size(min.y, max.y), fill)
+        }
+    }
+
+    /// Grow the grid to cover `pos`, filling new cells with `fill`
+    ///
+    /// Growing downwards only appends rows, which is amortized like a `Vec`;
+    /// growing in any other direction copies every cell.
+    pub fn include(&mut self, pos: Point2, fill: T) {
+        if self.contains(pos) {
+            return;
+        }
+
+        if self.is_empty() {
+            *self = Self::with_bounds(pos, pos, fill);
+            return;
+        }
+
+        let (old_min, old_max) = self.bounds();
+        let (min, max) = Point2::bounds([old_min, old_max, pos]).unwrap_or_default();
+
+        if min == old_min && max.x == old_max.x {
+            self.height = (max.y - min.y + 1) as usize;
+            self.cells.resize(self.width * self.height, fill);
+            return;
+        }
+
+        let mut grown = Self::with_bounds(min, max, fill);
+
+        for (pos, cell) in self.iter() {
+            if let Some(c) = grown.get_mut(pos) {
+                *c = cell.clone();
+            }
+        }
+
+        *self = grown;
+    }
+}
+
+impl<T> ops::Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point2) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos:?} is outside the grid"),
+        }
+    }
+}
+
+impl<T> ops::IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, pos: Point2) -> &mut T {
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos:?} is outside the grid"),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = r#"
+        123
+        456
+    "#;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, |c| c.to_digit(10).context("Not a digit"))
+    }
+
+    #[test]
+    fn parse() -> Result<(), super::Error> {
+        let grid = digits(INPUT)?;
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point2::new(2, 1)), Some(&6));
+        assert_eq!(grid.get(Point2::new(3, 1)), None);
+        assert_eq!(grid.get(Point2::new(-1, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456");
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = digits("123\n4x6").unwrap_err();
+
+        assert_eq!((err.line, err.columns), (2, 2..3));
+        assert_eq!(err.message, "Not a digit");
+
+        let err = digits("123\n45").unwrap_err();
+
+        assert_eq!(err.message, "Row 2 has 2 cells, expected 3");
+        assert!(digits("").is_ok_and(|g| g.is_empty()));
+    }
+
+    #[test]
+    fn iterators() -> Result<(), super::Error> {
+        let grid = digits(INPUT)?;
+
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        let (a, b) = (Point2::new(0, 0), Point2::new(1, 1));
+
+        assert_eq!(grid.ray(a, b).collect::<Vec<_>>(), [(b, &5)]);
+        assert_eq!(
+            grid.neighbors4(a).collect::<Vec<_>>(),
+            [Point2::new(1, 0), Point2::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors8(Point2::new(1, 0)).count(), 5);
+        assert_eq!(grid.iter().nth(4), Some((b, &5)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn include() {
+        let mut grid = Grid::with_bounds(Point2::new(0, 0), Point2::new(1, 0), '#');
+
+        grid.include(Point2::new(1, 2), '.');
+        assert_eq!(grid.to_string(), "##\n..\n..");
+
+        grid.include(Point2::new(-1, -1), '.');
+        assert_eq!(grid.bounds(), (Point2::new(-1, -1), Point2::new(1, 2)));
+        assert_eq!(grid.to_string(), "...\n.##\n...\n...");
+        assert_eq!(grid[Point2::new(0, 0)], '#');
+    }
+}
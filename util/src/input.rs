@@ -0,0 +1,169 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::*;
+
+// Discovery also searches the workspace so days can be run from any crate
+static WORKSPACE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+/// A puzzle input along with where it was read from
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Input {
+    pub name: String,
+    pub text: String,
+}
+
+/// Where to read puzzle inputs from
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    /// `-` on the command line
+    Stdin,
+    File(PathBuf),
+    /// `<dir>/day-NN.txt`, falling back to `days/day-NN/test/*.txt`
+    Discover(PathBuf),
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Source::Discover(PathBuf::from("inputs"))
+    }
+}
+
+impl FromStr for Source {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "-" => Source::Stdin,
+            path => Source::File(path.into()),
+        })
+    }
+}
+
+impl Source {
+    pub fn load(&self, day: u8) -> Result<Vec<Input>, Error> {
+        match self {
+            Source::Stdin => Ok(vec![Input {
+                name: "-".into(),
+                text: normalize(&read_stdin()?),
+            }]),
+            Source::File(path) => Ok(vec![read(path)?]),
+            Source::Discover(dir) => discover(dir, day)?.iter().map(|p| read(p)).collect(),
+        }
+    }
+}
+
+/// Path of the personal input for a day within `dir`
+pub fn path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day-{day:02}.txt"))
+}
+
+/// Read and normalize a single input file
+pub fn read(path: &Path) -> Result<Input, Error> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    Ok(Input {
+        name: path.display().to_string(),
+        text: normalize(&text),
+    })
+}
+
+/// Every file in a directory, sorted by path
+pub fn files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+fn samples(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut samples = files(dir)?;
+
+    // Expected outputs are stored next to the samples
+    samples.retain(|path| {
+        path.extension().is_some_and(|ext| ext == "txt")
+            && path.file_stem().is_some_and(|stem| stem != "output")
+    });
+
+    Ok(samples)
+}
+
+/// Find inputs for a day: the personal input in `dir` if it exists,
+/// otherwise every sample in the day's `test` directory
+pub fn discover(dir: &Path, day: u8) -> Result<Vec<PathBuf>, Error> {
+    let personal = path(dir, day);
+
+    if personal.is_file() {
+        return Ok(vec![personal]);
+    }
+
+    for root in [Path::new("."), Path::new(WORKSPACE)] {
+        let samples = samples(&root.join(format!("days/day-{day:02}/test")))?;
+
+        if !samples.is_empty() {
+            return Ok(samples);
+        }
+    }
+
+    bail!(
+        "No input for day {day}; expected {} or samples in days/day-{day:02}/test",
+        personal.display()
+    )
+}
+
+/// Convert CRLF line endings and strip trailing whitespace from every line
+pub fn normalize(text: &str) -> String {
+    text.trim_end()
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalize() {
+        assert_eq!(super::normalize("a  \r\n  b\r\n\r\n"), "a\n  b");
+        assert_eq!(super::normalize("    [D]    \n[N] [C]"), "    [D]\n[N] [C]");
+    }
+
+    #[test]
+    fn source() -> Result<(), Error> {
+        assert_eq!("-".parse::<Source>()?, Source::Stdin);
+        assert_eq!(
+            "in.txt".parse::<Source>()?,
+            Source::File(PathBuf::from("in.txt"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn discover_samples() -> Result<(), Error> {
+        let found = discover(Path::new("missing"), 10)?;
+
+        assert_eq!(found.len(), 1);
+        assert!(found[0].ends_with("days/day-10/test/sample.txt"));
+
+        Ok(())
+    }
+}
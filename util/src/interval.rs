@@ -0,0 +1,240 @@
+use crate::*;
+
+type Range = ops::RangeInclusive<isize>;
+
+/// Set of integers, stored as sorted, disjoint inclusive ranges
+///
+/// Overlapping or adjacent ranges are merged on insertion, so every set has
+/// exactly one representation.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntervalSet {
+    ranges: Vec<(isize, isize)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add every integer in `range`, merging it with its neighbours
+    pub fn insert(&mut self, range: Range) {
+        let (mut lo, mut hi) = range.into_inner();
+
+        if lo > hi {
+            return;
+        }
+
+        // Ranges touching `lo..=hi`, including those ending just before it
+        let start = self.ranges.partition_point(|r| r.1.saturating_add(1) < lo);
+        let end = self.ranges.partition_point(|r| r.0 <= hi.saturating_add(1));
+
+        if start < end {
+            lo = lo.min(self.ranges[start].0);
+            hi = hi.max(self.ranges[end - 1].1);
+        }
+
+        self.ranges.splice(start..end, [(lo, hi)]);
+    }
+
+    /// Remove every integer in `range`
+    pub fn remove(&mut self, range: Range) {
+        let (lo, hi) = range.into_inner();
+
+        if lo > hi {
+            return;
+        }
+
+        let start = self.ranges.partition_point(|r| r.1 < lo);
+        let end = self.ranges.partition_point(|r| r.0 <= hi);
+
+        if start == end {
+            return;
+        }
+
+        let first = self.ranges[start];
+        let last = self.ranges[end - 1];
+        let left = (first.0 < lo).then(|| (first.0, lo - 1));
+        let right = (last.1 > hi).then(|| (hi + 1, last.1));
+
+        self.ranges
+            .splice(start..end, left.into_iter().chain(right));
+    }
+
+    pub fn contains(&self, n: isize) -> bool {
+        self.contains_range(n..=n)
+    }
+
+    /// Whether every integer in a non-empty `range` is in the set
+    pub fn contains_range(&self, range: Range) -> bool {
+        let i = self.ranges.partition_point(|r| r.1 < *range.start());
+
+        self.ranges
+            .get(i)
+            .is_some_and(|r| r.0 <= *range.start() && *range.end() <= r.1)
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.ranges().all(|r| self.contains_range(r))
+    }
+
+    /// Whether the sets have any integer in common
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+
+        set.extend(other.ranges());
+        set
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(&&x), Some(&&y)) = (a.peek(), b.peek()) {
+            let (lo, hi) = (x.0.max(y.0), x.1.min(y.1));
+
+            if lo <= hi {
+                ranges.push((lo, hi));
+            }
+
+            // Whichever range ends first can't overlap anything further
+            if x.1 < y.1 {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+
+        for r in other.ranges() {
+            set.remove(r);
+        }
+
+        set
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of integers in the set
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(|r| r.0.abs_diff(r.1) + 1).sum()
+    }
+
+    /// The set's ranges in ascending order
+    pub fn ranges(&self) -> impl Iterator<Item = Range> + '_ {
+        self.ranges.iter().map(|&(lo, hi)| lo..=hi)
+    }
+
+    /// Ranges within `bounds` that are not in the set, in ascending order
+    pub fn gaps(&self, bounds: Range) -> impl Iterator<Item = Range> {
+        let gaps = Self::from_iter([bounds]).difference(self);
+
+        gaps.ranges.into_iter().map(|(lo, hi)| lo..=hi)
+    }
+}
+
+impl Extend<Range> for IntervalSet {
+    fn extend<I: IntoIterator<Item = Range>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl FromIterator<Range> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range>>(iter: I) -> Self {
+        let mut set = Self::new();
+
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ranges(set: &IntervalSet) -> Vec<Range> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn insert() {
+        let mut set = IntervalSet::from_iter([5..=7, 1..=2, 10..=12]);
+
+        assert_eq!(ranges(&set), [1..=2, 5..=7, 10..=12]);
+
+        set.insert(3..=4);
+        assert_eq!(ranges(&set), [1..=7, 10..=12]);
+
+        set.insert(6..=11);
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(20..=19);
+        assert_eq!(ranges(&set), [1..=12]);
+        assert_eq!(set.len(), 12);
+
+        set.insert(isize::MAX - 1..=isize::MAX);
+        assert_eq!(set.len(), 14);
+    }
+
+    #[test]
+    fn remove() {
+        let mut set = IntervalSet::from_iter([1..=10, 15..=20]);
+
+        set.remove(3..=4);
+        set.remove(9..=16);
+        assert_eq!(ranges(&set), [1..=2, 5..=8, 17..=20]);
+
+        set.remove(0..=30);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn queries() {
+        let set = IntervalSet::from_iter([1..=4, 8..=9]);
+
+        assert!(set.contains(4));
+        assert!(!set.contains(5));
+        assert!(set.contains_range(2..=4));
+        assert!(!set.contains_range(2..=8));
+        assert!(set.is_superset(&IntervalSet::from_iter([2..=3, 9..=9])));
+        assert!(!set.is_superset(&IntervalSet::from_iter([2..=5])));
+        assert!(set.overlaps(&IntervalSet::from_iter([5..=8])));
+        assert!(!set.overlaps(&IntervalSet::from_iter([5..=7])));
+    }
+
+    #[test]
+    fn operations() {
+        let a = IntervalSet::from_iter([1..=5, 10..=15]);
+        let b = IntervalSet::from_iter([4..=11, 14..=20]);
+
+        assert_eq!(ranges(&a.union(&b)), [1..=20]);
+        assert_eq!(ranges(&a.intersection(&b)), [4..=5, 10..=11, 14..=15]);
+        assert_eq!(ranges(&a.difference(&b)), [1..=3, 12..=13]);
+        assert_eq!(ranges(&b.difference(&a)), [6..=9, 16..=20]);
+    }
+
+    #[test]
+    fn gaps() {
+        let set = IntervalSet::from_iter([1..=4, 8..=9]);
+
+        assert_eq!(
+            set.gaps(0..=10).collect::<Vec<_>>(),
+            [0..=0, 5..=7, 10..=10]
+        );
+        assert_eq!(set.gaps(2..=3).count(), 0);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::*;
+
+/// Displays each item in order, separated by a string
+///
+/// Useful for writing inputs that are lists of records back out, e.g.
+/// `Joined(&monkeys, "\n\n")`.
+#[derive(Clone, Copy, Debug)]
+pub struct Joined<'a, T>(pub &'a [T], pub &'a str);
+
+impl<T: fmt::Display> fmt::Display for Joined<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, item) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(self.1)?;
+            }
+
+            write!(f, "{item}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn joined() {
+        assert_eq!(Joined(&[1, 2, 3], ", ").to_string(), "1, 2, 3");
+        assert_eq!(Joined::<u8>(&[], "\n").to_string(), "");
+    }
+}
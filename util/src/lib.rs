@@ -7,6 +7,37 @@ pub use std::{
 
 pub use anyhow::{bail, Context, Error};
 
+pub use error::{Located, ParseError};
+pub use format::{Format, Record};
+pub use generate::Generate;
+pub use geom::{Direction, Point2, Point3};
+pub use grid::Grid;
+pub use input::{Input, Source};
+pub use interval::IntervalSet;
+pub use joined::Joined;
+pub use rng::Rng;
+pub use sim::Simulation;
+pub use solution::{run, solve, Answers, Report, Solution, Timings};
+pub use viz::Visualize;
+
+pub mod cycle;
+pub mod differential;
+pub mod error;
+mod format;
+pub mod generate;
+pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+mod joined;
+pub mod parser;
+pub mod prop;
+pub mod rng;
+pub mod sim;
+mod solution;
+pub mod viz;
+
 pub fn read_stdin() -> Result<String, io::Error> {
     let mut buf = String::new();
     io::Read::read_to_string(&mut io::stdin(), &mut buf)?;
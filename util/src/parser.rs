@@ -0,0 +1,379 @@
+//! Small parser combinators for the puzzle text formats
+//!
+//! Parsers are functions from the remaining input to a value and the input
+//! left after it. Tokens skip any leading whitespace, including newlines, so
+//! grammars only describe the words that matter. Once an alternative has
+//! consumed input it is committed, so errors point at the deepest failure
+//! rather than the start of the record.
+
+use crate::*;
+
+/// Parsed value and the input remaining after it
+pub type PResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// Where and why a parser failed
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Failure<'a> {
+    /// Offending part of the input
+    pub span: &'a str,
+    /// Tokens that would have been accepted at `span`
+    pub expected: Vec<String>,
+    /// Why the input at `span` was rejected, if it was recognized at all
+    pub message: Option<String>,
+}
+
+impl<'a> Failure<'a> {
+    fn expected(s: &'a str, expected: impl fmt::Display) -> Self {
+        let s = s.trim_start();
+        let len = s.find(char::is_whitespace).unwrap_or(s.len());
+
+        Self {
+            span: &s[..len],
+            expected: vec![expected.to_string()],
+            message: None,
+        }
+    }
+
+    fn rejected(span: &'a str, message: impl fmt::Display) -> Self {
+        Self {
+            span,
+            expected: Vec::new(),
+            message: Some(message.to_string()),
+        }
+    }
+
+    /// Whether the failing parser got past the first token of `s`
+    fn consumed(&self, s: &str) -> bool {
+        self.span.as_ptr() > s.trim_start().as_ptr()
+    }
+}
+
+impl fmt::Display for Failure<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(message) = &self.message {
+            return write!(f, "{message}");
+        }
+
+        write!(f, "Expected ")?;
+
+        for (i, expected) in self.expected.iter().enumerate() {
+            match i {
+                0 => {}
+                i if i + 1 == self.expected.len() => write!(f, " or ")?,
+                _ => write!(f, ", ")?,
+            }
+
+            write!(f, "{expected}")?;
+        }
+
+        Ok(())
+    }
+}
+
+pub trait Parser<'a, T>: Sized {
+    fn parse(&self, s: &'a str) -> PResult<'a, T>;
+
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+        move |s: &'a str| self.parse(s).map(|(t, rest)| (f(t), rest))
+    }
+
+    /// Map the value, rejecting everything this parser consumed on error
+    fn try_map<U, E: fmt::Display>(self, f: impl Fn(T) -> Result<U, E>) -> impl Parser<'a, U> {
+        move |s: &'a str| {
+            let (t, rest) = self.parse(s)?;
+            let s = s.trim_start();
+            let span = &s[..(s.len() - rest.len())];
+
+            match f(t) {
+                Ok(u) => Ok((u, rest)),
+                Err(err) => Err(Failure::rejected(span, err)),
+            }
+        }
+    }
+
+    /// Parse `next` after this, keeping only this parser's value
+    fn skip<U>(self, next: impl Parser<'a, U>) -> impl Parser<'a, T> {
+        move |s: &'a str| {
+            let (t, rest) = self.parse(s)?;
+            let (_, rest) = next.parse(rest)?;
+
+            Ok((t, rest))
+        }
+    }
+
+    /// Try `other` if this fails before consuming any input
+    fn or(self, other: impl Parser<'a, T>) -> impl Parser<'a, T> {
+        move |s: &'a str| match self.parse(s) {
+            Err(a) if !a.consumed(s) => match other.parse(s) {
+                Err(mut b) if !b.consumed(s) && a.message.is_none() => {
+                    b.expected.splice(0..0, a.expected);
+                    Err(b)
+                }
+                result => result,
+            },
+            result => result,
+        }
+    }
+
+    /// Describe what this parser expects when it fails at its first token
+    fn label(self, name: &'static str) -> impl Parser<'a, T> {
+        move |s: &'a str| {
+            self.parse(s).map_err(|failure| {
+                if failure.consumed(s) {
+                    failure
+                } else {
+                    Failure::expected(s, name)
+                }
+            })
+        }
+    }
+}
+
+impl<'a, T, F: Fn(&'a str) -> PResult<'a, T>> Parser<'a, T> for F {
+    fn parse(&self, s: &'a str) -> PResult<'a, T> {
+        self(s)
+    }
+}
+
+macro_rules! sequence {
+    ($($value:ident $parser:ident),+) => {
+        /// Parsers in sequence, producing a tuple of their values
+        #[allow(non_snake_case)]
+        impl<'a, $($value, $parser: Parser<'a, $value>),+> Parser<'a, ($($value,)+)>
+            for ($($parser,)+)
+        {
+            fn parse(&self, s: &'a str) -> PResult<'a, ($($value,)+)> {
+                let ($($parser,)+) = self;
+                let rest = s;
+                $(let ($value, rest) = $parser.parse(rest)?;)+
+
+                Ok((($($value,)+), rest))
+            }
+        }
+    };
+}
+
+sequence!(A PA, B PB);
+sequence!(A PA, B PB, C PC);
+sequence!(A PA, B PB, C PC, D PD);
+sequence!(A PA, B PB, C PC, D PD, E PE);
+sequence!(A PA, B PB, C PC, D PD, E PE, F PF);
+
+/// Match the words of `tag`, separated by any whitespace
+///
+/// Tags are atomic: a partial match fails at the start of the tag.
+pub fn tag<'a>(tag: &'static str) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| {
+        let start = s.trim_start();
+        let mut rest = start;
+
+        for word in tag.split_whitespace() {
+            rest = rest
+                .trim_start()
+                .strip_prefix(word)
+                .ok_or_else(|| Failure::expected(start, format!("\"{tag}\"")))?;
+        }
+
+        Ok((&start[..(start.len() - rest.len())], rest))
+    }
+}
+
+/// Decimal integer with an optional minus sign
+pub fn int<'a, T>() -> impl Parser<'a, T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    move |s: &'a str| {
+        let s = s.trim_start();
+        let sign = usize::from(s.starts_with('-'));
+        let len = s[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(s.len() - sign);
+
+        if len == 0 {
+            return Err(Failure::expected(s, "integer"));
+        }
+
+        let (digits, rest) = s.split_at(sign + len);
+
+        match digits.parse() {
+            Ok(n) => Ok((n, rest)),
+            Err(err) => Err(Failure::rejected(digits, err)),
+        }
+    }
+}
+
+/// Run of letters and digits, such as a name
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    move |s: &'a str| {
+        let s = s.trim_start();
+        let len = s.find(|c: char| !c.is_alphanumeric()).unwrap_or(s.len());
+
+        if len == 0 {
+            return Err(Failure::expected(s, "name"));
+        }
+
+        Ok(s.split_at(len))
+    }
+}
+
+/// `p` after the tag `prefix`
+pub fn prefixed<'a, T>(prefix: &'static str, p: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |s: &'a str| {
+        let (_, rest) = tag(prefix).parse(s)?;
+
+        p.parse(rest)
+    }
+}
+
+/// One or more `p` separated by the tag `sep`
+pub fn list<'a, T>(p: impl Parser<'a, T>, sep: &'static str) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let (first, mut rest) = p.parse(s)?;
+        let mut items = vec![first];
+
+        while let Ok((_, next)) = tag(sep).parse(rest) {
+            let (item, next) = p.parse(next)?;
+
+            items.push(item);
+            rest = next;
+        }
+
+        Ok((items, rest))
+    }
+}
+
+/// Zero or more `p` making up the rest of the input
+pub fn many_to_end<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let mut items = Vec::new();
+        let mut rest = s;
+
+        while !rest.trim().is_empty() {
+            let (item, next) = p.parse(rest)?;
+
+            items.push(item);
+            rest = next;
+        }
+
+        Ok((items, rest))
+    }
+}
+
+/// Only whitespace remaining
+pub fn end<'a>() -> impl Parser<'a, ()> {
+    move |s: &'a str| {
+        if s.trim().is_empty() {
+            Ok(((), ""))
+        } else {
+            Err(Failure::expected(s, "end of input"))
+        }
+    }
+}
+
+/// Parse all of `s`, a slice of `input`, reporting failures within `input`
+pub fn run<'a, T>(input: &'a str, s: &'a str, p: impl Parser<'a, T>) -> Result<T, ParseError> {
+    p.skip(end())
+        .parse(s)
+        .map(|(t, _)| t)
+        .map_err(|failure| ParseError::new(input, failure.span, &failure))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse<'a, T>(s: &'a str, p: impl Parser<'a, T>) -> Result<T, ParseError> {
+        run(s, s, p)
+    }
+
+    #[test]
+    fn tokens() -> Result<(), super::Error> {
+        let p = (
+            prefixed("move", int::<usize>()),
+            prefixed("from  the", word()),
+        );
+
+        assert_eq!(parse("move 3\nfrom the   AB", p)?, (3, "AB"));
+        assert_eq!(parse(" -12 ", int::<i8>())?, -12);
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        let err = parse(
+            "move 3 from\nthe x",
+            (tag("move"), int::<u8>(), tag("from the"), int::<u8>()),
+        )
+        .unwrap_err();
+
+        assert_eq!((err.line, err.columns.clone()), (2, 5..6));
+        assert_eq!(err.message, "Expected integer");
+
+        let err = parse("1000", int::<u8>()).unwrap_err();
+
+        assert_eq!(err.message, "number too large to fit in target type");
+        assert_eq!(err.columns, 1..5);
+
+        let err = parse("1 2", int::<u8>()).unwrap_err();
+
+        assert_eq!(
+            (err.columns, err.message.as_str()),
+            (3..4, "Expected end of input")
+        );
+    }
+
+    #[test]
+    fn alternatives() -> Result<(), super::Error> {
+        let op = || {
+            prefixed("*", tag("old").map(|_| None).or(int().map(Some)))
+                .or(prefixed("+", int().map(|n: i32| Some(-n))))
+                .label("operation")
+        };
+
+        assert_eq!(parse("* old", op())?, None);
+        assert_eq!(parse("* 3", op())?, Some(3));
+        assert_eq!(parse("+ 3", op())?, Some(-3));
+        assert_eq!(
+            parse("/ 3", op()).unwrap_err().message,
+            "Expected operation"
+        );
+        assert_eq!(
+            parse("* x", op()).unwrap_err().message,
+            "Expected \"old\" or integer"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lists() -> Result<(), super::Error> {
+        let p = many_to_end(prefixed("Items:", list(int::<u8>(), ",")));
+
+        assert_eq!(parse("Items: 1, 2\nItems: 3", p)?, [vec![1, 2], vec![3]]);
+
+        let p = many_to_end(prefixed("Items:", list(int::<u8>(), ",")));
+        let err = parse("Items: 1, 2\nItems: 3,", p).unwrap_err();
+
+        assert_eq!((err.line, err.message.as_str()), (2, "Expected integer"));
+
+        let p = many_to_end(word().skip(tag(";")));
+
+        assert_eq!(parse("", p)?, Vec::<&str>::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn try_map() {
+        let p = prefixed(
+            "cost",
+            int::<u32>().try_map(|n| std::num::NonZeroU32::new(n).context("Free")),
+        );
+        let err = parse("cost  0", p).unwrap_err();
+
+        assert_eq!((err.columns, err.message.as_str()), (7..8, "Free"));
+    }
+}
@@ -0,0 +1,49 @@
+//! Helpers for property tests over randomly generated inputs
+
+use crate::rng::Rng;
+use crate::*;
+
+/// Random cases tried by [`check`]
+pub const CASES: u64 = 100;
+
+/// Run `test` with a generator seeded from each of `0..CASES`
+///
+/// Panics with the failing seed, so a case can be replayed with
+/// `Rng::new(seed)`.
+pub fn check(test: impl FnMut(&mut Rng) -> Result<(), Error>) {
+    check_cases(CASES, test);
+}
+
+/// [`check`] with fewer cases, for properties that are slow to test
+pub fn check_cases(cases: u64, mut test: impl FnMut(&mut Rng) -> Result<(), Error>) {
+    for seed in 0..cases {
+        if let Err(err) = test(&mut Rng::new(seed)) {
+            panic!("Failed with seed {seed}: {err:?}");
+        }
+    }
+}
+
+/// Fail unless `actual` matches `expected`, showing both
+pub fn equal<T: fmt::Debug + PartialEq>(actual: &T, expected: &T) -> Result<(), Error> {
+    if actual != expected {
+        bail!("Expected {expected:#?}\nbut got {actual:#?}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check() {
+        super::check(|rng| equal(&(rng.below(10) < 10), &true));
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed with seed 0")]
+    fn failure() {
+        super::check(|_| equal(&1, &2));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::*;
+
+/// Small deterministic random number generator (SplitMix64)
+///
+/// Not suitable for anything but tests and generated puzzle inputs, where
+/// being reproducible from a seed matters more than quality.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform integer below `n`, which must not be zero
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Empty range");
+
+        // Multiply-shift keeps the bias negligible for the sizes used here
+        ((u128::from(self.next_u64()) * n as u128) >> 64) as usize
+    }
+
+    /// Uniform integer in `range`, which must not be empty
+    pub fn range(&mut self, range: ops::RangeInclusive<isize>) -> isize {
+        let (lo, hi) = range.into_inner();
+
+        assert!(lo <= hi, "Empty range");
+
+        match (hi.abs_diff(lo)).checked_add(1) {
+            Some(n) => lo.wrapping_add_unsigned(self.below(n)),
+            None => self.next_u64() as isize,
+        }
+    }
+
+    /// `true` with probability `p`
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// Uniformly chosen element of a non-empty slice
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(0);
+        let mut seen = HashSet::new();
+
+        for _ in 0..1000 {
+            let n = rng.range(-2..=2);
+
+            assert!((-2..=2).contains(&n));
+            seen.insert(n);
+            assert!(rng.below(3) < 3);
+        }
+
+        assert_eq!(seen.len(), 5);
+        assert_eq!(rng.range(4..=4), 4);
+        rng.range(isize::MIN..=isize::MAX);
+    }
+
+    #[test]
+    fn shuffle() {
+        let mut rng = Rng::new(3);
+        let mut items = (0..20).collect::<Vec<_>>();
+
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
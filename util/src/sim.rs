@@ -0,0 +1,147 @@
+use crate::*;
+
+/// Simulation that can be single-stepped, inspected and rewound
+///
+/// Everything that changes as it runs lives in its [`State`](Self::State),
+/// so restoring a snapshot and stepping again replays the same states.
+pub trait Simulation {
+    type State: Clone;
+
+    /// Advance one step, or return `false` and change nothing once finished
+    fn step(&mut self) -> Result<bool, Error>;
+
+    fn state(&self) -> &Self::State;
+
+    /// Go back, or forward, to a snapshot taken earlier
+    fn restore(&mut self, snapshot: Self::State);
+
+    fn snapshot(&self) -> Self::State {
+        self.state().clone()
+    }
+
+    /// Step until `done` holds, returning whether it did before finishing
+    fn run_until(&mut self, mut done: impl FnMut(&Self::State) -> bool) -> Result<bool, Error>
+    where
+        Self: Sized,
+    {
+        while !done(self.state()) {
+            if !self.step()? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// The current state, and that after each of up to `steps` further steps
+pub fn trace<S: Simulation>(sim: &mut S, steps: usize) -> Result<Vec<S::State>, Error> {
+    let mut states = vec![sim.snapshot()];
+
+    for _ in 0..steps {
+        if !sim.step()? {
+            break;
+        }
+
+        states.push(sim.snapshot());
+    }
+
+    Ok(states)
+}
+
+/// Check that `sim` replays the same `steps` states from each snapshot
+pub fn check_replay<S>(sim: &mut S, steps: usize)
+where
+    S: Simulation,
+    S::State: PartialEq + fmt::Debug,
+{
+    let states = trace(sim, steps).expect("Simulation failed");
+
+    for (i, snapshot) in states.iter().enumerate() {
+        sim.restore(snapshot.clone());
+
+        let replay = trace(sim, states.len() - 1 - i).expect("Replay failed");
+
+        assert!(replay == states[i..], "Replay from step {i} diverged");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Counts down to zero
+    struct Countdown(u32);
+
+    impl Simulation for Countdown {
+        type State = u32;
+
+        fn step(&mut self) -> Result<bool, Error> {
+            let Some(n) = self.0.checked_sub(1) else {
+                return Ok(false);
+            };
+
+            self.0 = n;
+            Ok(true)
+        }
+
+        fn state(&self) -> &u32 {
+            &self.0
+        }
+
+        fn restore(&mut self, snapshot: u32) {
+            self.0 = snapshot;
+        }
+    }
+
+    #[test]
+    fn run_until() -> Result<(), Error> {
+        let mut sim = Countdown(10);
+
+        assert!(sim.run_until(|&n| n == 4)?);
+        assert!(!sim.run_until(|&n| n > 4)?);
+        assert_eq!(sim.0, 0);
+        assert!(!sim.step()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn replay() -> Result<(), Error> {
+        let mut sim = Countdown(3);
+
+        assert_eq!(trace(&mut sim, 10)?, [3, 2, 1, 0]);
+
+        sim.restore(2);
+
+        assert_eq!(trace(&mut sim, 1)?, [2, 1]);
+
+        check_replay(&mut Countdown(5), 10);
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Replay from step 0 diverged")]
+    fn diverged() {
+        /// Forgets to restore
+        struct Broken(u32);
+
+        impl Simulation for Broken {
+            type State = u32;
+
+            fn step(&mut self) -> Result<bool, Error> {
+                self.0 += 1;
+                Ok(true)
+            }
+
+            fn state(&self) -> &u32 {
+                &self.0
+            }
+
+            fn restore(&mut self, _: u32) {}
+        }
+
+        check_replay(&mut Broken(0), 3);
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    env,
+    fmt::Write,
+    io::IsTerminal,
+    time::{Duration, Instant},
+};
+
+use crate::*;
+
+/// A single day's puzzle, split into parsing and the two parts
+///
+/// Both parts borrow the parsed input so that it only needs to be parsed
+/// once, and both are fallible so that callers can treat every day alike.
+pub trait Solution {
+    /// Day of the advent calendar, starting at 1
+    const DAY: u8;
+
+    /// Parsed puzzle input; may borrow from the raw input text
+    type Input<'a>;
+    type Output1: fmt::Display;
+    type Output2: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error>;
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error>;
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error>;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Answers {
+    pub part_1: String,
+    pub part_2: String,
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_part(f, 1, &self.part_1)?;
+        f.write_char('\n')?;
+        write_part(f, 2, &self.part_2)
+    }
+}
+
+// Multi-line answers (e.g., rendered text) start on their own line
+fn write_part(f: &mut fmt::Formatter, part: u8, answer: &str) -> fmt::Result {
+    if answer.contains('\n') {
+        write!(f, "Part {part}:\n{answer}")
+    } else {
+        write!(f, "Part {part}: {answer}")
+    }
+}
+
+/// How long each phase of a solution took
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_1: Duration,
+    pub part_2: Duration,
+}
+
+/// Answers for a single day along with their timings
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub day: u8,
+    pub answers: Answers,
+    pub timings: Timings,
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+
+    (res, start.elapsed())
+}
+
+/// Parse the input and solve both parts, formatting the answers
+pub fn solve<S: Solution>(input: &str) -> Result<Report, Error> {
+    let (input, parse) = timed(|| S::parse(input));
+    let input = input?;
+    let (part_1, part_1_time) = timed(|| S::part_1(&input));
+    let part_1 = part_1?.to_string();
+    let (part_2, part_2_time) = timed(|| S::part_2(&input));
+    let part_2 = part_2?.to_string();
+
+    Ok(Report {
+        day: S::DAY,
+        answers: Answers { part_1, part_2 },
+        timings: Timings {
+            parse,
+            part_1: part_1_time,
+            part_2: part_2_time,
+        },
+    })
+}
+
+/// Entrypoint shared by each day's binary
+///
+/// Accepts an input file (or `-` for `stdin`) and `--format <text|json>`.
+/// Without a file, piped input is read from `stdin` and inputs are
+/// discovered otherwise.
+pub fn run<S: Solution>() -> Result<(), Error> {
+    run_with::<S>(env::args().skip(1).collect())
+}
+
+/// Where inputs come from when no source is given
+pub(crate) fn default_source() -> Source {
+    if io::stdin().is_terminal() {
+        Source::default()
+    } else {
+        Source::Stdin
+    }
+}
+
+/// [`run`] with the given command line arguments
+pub(crate) fn run_with<S: Solution>(args: Vec<String>) -> Result<(), Error> {
+    let mut format = Format::Text;
+    let mut source = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => format = args.next().context("Missing format")?.parse()?,
+            _ if source.is_none() => source = Some(arg.parse()?),
+            _ => bail!("Unexpected argument: {arg}"),
+        }
+    }
+
+    let source = source.unwrap_or_else(default_source);
+    let inputs = source.load(S::DAY)?;
+
+    for (i, input) in inputs.iter().enumerate() {
+        if inputs.len() > 1 && format == Format::Text {
+            if i > 0 {
+                println!();
+            }
+
+            println!("{}", input.name);
+        }
+
+        let report = solve::<S>(&input.text).with_context(|| input.name.clone())?;
+
+        println!("{}", format.render(&report)?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        const DAY: u8 = 0;
+
+        type Input<'a> = Vec<&'a str>;
+        type Output1 = usize;
+        type Output2 = String;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+            Ok(input.lines().collect())
+        }
+
+        fn part_1(input: &Self::Input<'_>) -> Result<Self::Output1, Error> {
+            Ok(input.len())
+        }
+
+        fn part_2(input: &Self::Input<'_>) -> Result<Self::Output2, Error> {
+            Ok(input.join("\n"))
+        }
+    }
+
+    #[test]
+    fn solve() -> Result<(), Error> {
+        let report = super::solve::<Lines>("a\nb")?;
+
+        assert_eq!(report.day, 0);
+        assert_eq!(report.answers.part_1, "2");
+        assert_eq!(report.answers.to_string(), "Part 1: 2\nPart 2:\na\nb");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,412 @@
+//! Watching simulations, in a terminal or as images to share
+
+use std::{
+    env,
+    fmt::Write as _,
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::*;
+
+pub use gif::Gif;
+pub use png::{write_png, write_ppm};
+
+mod gif;
+mod png;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+}
+
+/// A picture of a simulation's state, one colored cell per position
+pub trait Frame {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+
+    /// Color of the cell in column `x` and row `y`, from the top left
+    fn pixel(&self, x: usize, y: usize) -> Rgb;
+}
+
+/// Frame stored cell by cell, row by row
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    /// Picture of a grid, coloring each cell with `color`
+    pub fn from_grid<T>(grid: &Grid<T>, color: impl Fn(&T) -> Rgb) -> Self {
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.iter().map(|(_, cell)| color(cell)).collect(),
+        }
+    }
+
+    /// Copy of any other frame
+    pub fn capture(frame: &dyn Frame) -> Self {
+        let (width, height) = (frame.width(), frame.height());
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| frame.pixel(x, y)))
+            .collect();
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Color the cell at `p`, if it's within the image
+    pub fn set(&mut self, p: Point2, color: Rgb) {
+        let x = usize::try_from(p.x).ok().filter(|&x| x < self.width);
+        let y = usize::try_from(p.y).ok().filter(|&y| y < self.height);
+
+        if let (Some(x), Some(y)) = (x, y) {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+}
+
+impl Frame for Image {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+}
+
+/// A frame drawn `n` times larger, each cell a square of `n` by `n` pixels
+pub struct Scaled<'a>(pub &'a dyn Frame, pub usize);
+
+impl Frame for Scaled<'_> {
+    fn width(&self) -> usize {
+        self.0.width() * self.1
+    }
+
+    fn height(&self) -> usize {
+        self.0.height() * self.1
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.0.pixel(x / self.1, y / self.1)
+    }
+}
+
+/// Where a simulation's frames go, one after another
+pub trait Recorder {
+    fn record(&mut self, frame: &dyn Frame) -> Result<(), Error>;
+
+    /// Called once after the last frame
+    fn finish(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Keeps every frame, e.g. to test visualizations with
+impl Recorder for Vec<Image> {
+    fn record(&mut self, frame: &dyn Frame) -> Result<(), Error> {
+        self.push(Image::capture(frame));
+        Ok(())
+    }
+}
+
+/// Plays frames in a terminal using ANSI escapes, two rows per line
+pub struct Animator<W: Write> {
+    out: W,
+    interval: Duration,
+    next: Option<Instant>,
+}
+
+impl<W: Write> Animator<W> {
+    /// Animator showing `fps` frames a second, or as many as it can if zero
+    pub fn new(out: W, fps: u32) -> Self {
+        let interval = match fps {
+            0 => Duration::ZERO,
+            fps => Duration::from_secs(1) / fps,
+        };
+
+        Self {
+            out,
+            interval,
+            next: None,
+        }
+    }
+}
+
+impl<W: Write> Recorder for Animator<W> {
+    fn record(&mut self, frame: &dyn Frame) -> Result<(), Error> {
+        match self.next {
+            Some(next) => thread::sleep(next.saturating_duration_since(Instant::now())),
+            // Clear the screen once, then draw every frame over the last
+            None => self.out.write_all(b"\x1b[2J")?,
+        }
+
+        let mut buf = String::from("\x1b[H");
+
+        for y in (0..frame.height()).step_by(2) {
+            let mut colors = None;
+
+            for x in 0..frame.width() {
+                let top = frame.pixel(x, y);
+                let bottom = if y + 1 < frame.height() {
+                    frame.pixel(x, y + 1)
+                } else {
+                    Rgb::BLACK
+                };
+
+                if colors != Some((top, bottom)) {
+                    let (Rgb(r, g, b), Rgb(br, bg, bb)) = (top, bottom);
+
+                    write!(buf, "\x1b[38;2;{r};{g};{b};48;2;{br};{bg};{bb}m")?;
+                    colors = Some((top, bottom));
+                }
+
+                buf.push('▀');
+            }
+
+            buf.push_str("\x1b[0m\n");
+        }
+
+        self.out.write_all(buf.as_bytes())?;
+        self.out.flush()?;
+
+        let now = Instant::now();
+
+        self.next = Some(self.next.map_or(now, |next| next.max(now)) + self.interval);
+
+        Ok(())
+    }
+}
+
+/// Keeps the last frame, writing it as a still image when finished
+pub struct Still<W: Write> {
+    out: W,
+    write: fn(&mut dyn Write, &dyn Frame) -> Result<(), Error>,
+    scale: usize,
+    last: Option<Image>,
+}
+
+impl<W: Write> Still<W> {
+    /// Still image written by `write`, e.g. [`write_png`], `scale` times
+    /// larger than the frames
+    pub fn new(
+        out: W,
+        write: fn(&mut dyn Write, &dyn Frame) -> Result<(), Error>,
+        scale: usize,
+    ) -> Self {
+        Self {
+            out,
+            write,
+            scale: scale.max(1),
+            last: None,
+        }
+    }
+}
+
+impl<W: Write> Recorder for Still<W> {
+    fn record(&mut self, frame: &dyn Frame) -> Result<(), Error> {
+        self.last = Some(Image::capture(frame));
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        let last = self.last.take().context("No frames")?;
+
+        (self.write)(&mut self.out, &Scaled(&last, self.scale))?;
+        self.out.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Days whose solutions can be watched
+pub trait Visualize: Solution {
+    /// Record how solving `input` goes, frame by frame
+    fn visualize(input: &Self::Input<'_>, recorder: &mut dyn Recorder) -> Result<(), Error>;
+}
+
+/// Remove `--name <value>` from `args`, parsing the value
+fn take_flag<T>(args: &mut Vec<String>, name: &str) -> Result<Option<T>, Error>
+where
+    T: FromStr,
+    T::Err: Into<Error>,
+{
+    let Some(i) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+
+    args.remove(i);
+
+    if i == args.len() {
+        bail!("Missing value for {name}");
+    }
+
+    let value = args.remove(i);
+
+    value
+        .parse()
+        .map(Some)
+        .map_err(Into::into)
+        .with_context(|| format!("Invalid value for {name}: {value}"))
+}
+
+fn recorder(target: Option<PathBuf>, fps: u32, scale: usize) -> Result<Box<dyn Recorder>, Error> {
+    let Some(path) = target else {
+        return Ok(Box::new(Animator::new(io::stdout(), fps)));
+    };
+
+    let file =
+        File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let out = BufWriter::new(file);
+
+    Ok(match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gif") => Box::new(Gif::new(out, fps, scale)),
+        Some("png") => Box::new(Still::new(out, write_png, scale)),
+        _ => Box::new(Still::new(out, write_ppm, scale)),
+    })
+}
+
+fn is_image(arg: &str) -> bool {
+    [".gif", ".png", ".ppm"]
+        .iter()
+        .any(|ext| arg.ends_with(ext))
+}
+
+/// Entrypoint of days that can be visualized
+///
+/// Like [`crate::run`], unless given `--visualize`: then the first input is played
+/// in the terminal instead of solved, or with `--visualize <file>` written
+/// to an animated `.gif` or its last frame to a `.png` or `.ppm`. `--fps`
+/// (default 30) sets the frame rate and `--scale` (default 4) the size of
+/// each cell in images.
+pub fn run<S: Visualize>() -> Result<(), Error> {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let Some(i) = args.iter().position(|arg| arg == "--visualize") else {
+        return solution::run_with::<S>(args);
+    };
+
+    args.remove(i);
+
+    let target = match args.get(i) {
+        Some(arg) if is_image(arg) => Some(PathBuf::from(args.remove(i))),
+        _ => None,
+    };
+    let fps = take_flag(&mut args, "--fps")?.unwrap_or(30);
+    let scale = take_flag(&mut args, "--scale")?.unwrap_or(4);
+
+    let source = match &args[..] {
+        [] => solution::default_source(),
+        [source] => source.parse()?,
+        [_, arg, ..] => bail!("Unexpected argument: {arg}"),
+    };
+    let inputs = source.load(S::DAY)?;
+    let input = inputs.first().context("No input")?;
+    let input = S::parse(&input.text).with_context(|| input.name.clone())?;
+    let mut recorder = recorder(target, fps, scale)?;
+
+    S::visualize(&input, recorder.as_mut())?;
+    recorder.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checkers() -> Image {
+        let mut image = Image::new(3, 3, Rgb::BLACK);
+
+        for p in [(0, 0), (2, 0), (1, 1), (0, 2), (2, 2)] {
+            image.set(p.into(), Rgb::WHITE);
+        }
+
+        image
+    }
+
+    #[test]
+    fn image() {
+        let image = checkers();
+        let scaled = Image::capture(&Scaled(&image, 2));
+
+        assert_eq!((scaled.width(), scaled.height()), (6, 6));
+        assert_eq!(scaled.pixel(3, 2), Rgb::WHITE);
+        assert_eq!(scaled.pixel(2, 1), Rgb::BLACK);
+
+        let grid = Grid::new(2, 1, true);
+
+        assert_eq!(
+            Image::from_grid(&grid, |&on| if on { Rgb::WHITE } else { Rgb::BLACK }),
+            Image::new(2, 1, Rgb::WHITE)
+        );
+    }
+
+    #[test]
+    fn animator() -> Result<(), Error> {
+        let mut out = Vec::new();
+        let mut animator = Animator::new(&mut out, 0);
+
+        animator.record(&checkers())?;
+        animator.record(&Image::new(1, 1, Rgb(1, 2, 3)))?;
+
+        let out = String::from_utf8(out)?;
+
+        // The second frame is drawn over the first without clearing again
+        assert_eq!(out.matches("\x1b[2J").count(), 1);
+        assert_eq!(out.matches("\x1b[H").count(), 2);
+        assert_eq!(out.matches('▀').count(), 3 * 2 + 1);
+        assert!(out.ends_with("\x1b[38;2;1;2;3;48;2;0;0;0m▀\x1b[0m\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn still() -> Result<(), Error> {
+        let mut out = Vec::new();
+        let mut still = Still::new(&mut out, write_ppm, 2);
+
+        still.record(&Image::new(5, 5, Rgb::BLACK))?;
+        still.record(&checkers())?;
+        still.finish()?;
+
+        assert!(out.starts_with(b"P6\n6 6\n255\n"));
+        assert!(Still::new(Vec::new(), write_ppm, 1).finish().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn flags() -> Result<(), Error> {
+        let mut args = ["a", "--fps", "10", "b"].map(String::from).to_vec();
+
+        assert_eq!(take_flag::<u32>(&mut args, "--fps")?, Some(10));
+        assert_eq!(take_flag::<u32>(&mut args, "--scale")?, None);
+        assert_eq!(args, ["a", "b"]);
+        assert!(take_flag::<u32>(&mut vec!["--fps".into()], "--fps").is_err());
+
+        Ok(())
+    }
+}
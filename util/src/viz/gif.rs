@@ -0,0 +1,338 @@
+//! Animated GIFs, with an LZW encoder of their own
+
+use std::io::Write;
+
+use super::{Frame, Recorder, Rgb, Scaled};
+use crate::*;
+
+/// Writes frames to a looping animated GIF, each with its own palette
+pub struct Gif<W: Write> {
+    out: W,
+    /// Hundredths of a second between frames
+    delay: u16,
+    scale: usize,
+    size: Option<(u16, u16)>,
+}
+
+impl<W: Write> Gif<W> {
+    /// GIF showing `fps` frames a second, drawn `scale` times larger
+    ///
+    /// Viewers slow down anything faster than 50 frames a second, as does
+    /// an `fps` of zero.
+    pub fn new(out: W, fps: u32, scale: usize) -> Self {
+        let delay = 100u32.checked_div(fps).unwrap_or(0).max(2) as u16;
+
+        Self {
+            out,
+            delay,
+            scale: scale.max(1),
+            size: None,
+        }
+    }
+
+    fn header(&mut self, width: u16, height: u16) -> Result<(), Error> {
+        self.out.write_all(b"GIF89a")?;
+        self.out.write_all(&width.to_le_bytes())?;
+        self.out.write_all(&height.to_le_bytes())?;
+        // No global color table, background color and aspect ratio
+        self.out.write_all(&[0, 0, 0])?;
+
+        // Loop forever
+        self.out
+            .write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\0\0\0")?;
+
+        Ok(())
+    }
+}
+
+impl<W: Write> Recorder for Gif<W> {
+    fn record(&mut self, frame: &dyn Frame) -> Result<(), Error> {
+        let frame = Scaled(frame, self.scale);
+        let width = u16::try_from(frame.width()).context("Frame too wide for a GIF")?;
+        let height = u16::try_from(frame.height()).context("Frame too tall for a GIF")?;
+
+        if width == 0 || height == 0 {
+            bail!("Empty frame");
+        }
+
+        match self.size {
+            None => {
+                self.header(width, height)?;
+                self.size = Some((width, height));
+            }
+            Some(size) if size != (width, height) => {
+                bail!("Frame is {width}x{height}, unlike the first")
+            }
+            Some(_) => {}
+        }
+
+        let mut palette = Vec::<Rgb>::new();
+        let mut indices = HashMap::new();
+        let mut pixels = Vec::with_capacity(frame.width() * frame.height());
+
+        for y in 0..frame.height() {
+            for x in 0..frame.width() {
+                let color = frame.pixel(x, y);
+                let i = *indices.entry(color).or_insert_with(|| {
+                    palette.push(color);
+                    palette.len() - 1
+                });
+
+                pixels.push(i as u8);
+            }
+        }
+
+        if palette.len() > 256 {
+            bail!("Frame has {} colors, more than a GIF's 256", palette.len());
+        }
+
+        // Color tables hold a power of two colors, at least two
+        let bits = palette.len().next_power_of_two().trailing_zeros().max(1);
+
+        palette.resize(1 << bits, Rgb::BLACK);
+
+        let [lo, hi] = self.delay.to_le_bytes();
+
+        // Graphic control extension, to set the delay
+        self.out.write_all(&[0x21, 0xf9, 4, 0, lo, hi, 0, 0])?;
+
+        // Image descriptor, covering the whole screen, with a local color table
+        self.out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        self.out.write_all(&width.to_le_bytes())?;
+        self.out.write_all(&height.to_le_bytes())?;
+        self.out.write_all(&[0x80 | (bits - 1) as u8])?;
+
+        for Rgb(r, g, b) in palette {
+            self.out.write_all(&[r, g, b])?;
+        }
+
+        let min = bits.max(2) as u8;
+
+        self.out.write_all(&[min])?;
+
+        for block in lzw(&pixels, min).chunks(255) {
+            self.out.write_all(&[block.len() as u8])?;
+            self.out.write_all(block)?;
+        }
+
+        self.out.write_all(&[0])?;
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        if self.size.is_none() {
+            bail!("No frames");
+        }
+
+        self.out.write_all(&[0x3b])?;
+        self.out.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Packs codes of varying width, least significant bit first
+#[derive(Default)]
+struct Bits {
+    bytes: Vec<u8>,
+    acc: u32,
+    len: u32,
+}
+
+impl Bits {
+    fn push(&mut self, code: u16, width: u32) {
+        self.acc |= (code as u32) << self.len;
+        self.len += width;
+
+        while self.len >= 8 {
+            self.bytes.push(self.acc as u8);
+            self.acc >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.acc as u8);
+        }
+
+        self.bytes
+    }
+}
+
+/// Compresses color `indices` as GIF image data with `min` bit codes
+fn lzw(indices: &[u8], min: u8) -> Vec<u8> {
+    const MAX: u16 = 1 << 12;
+
+    let clear = 1u16 << min;
+    let end = clear + 1;
+    let mut bits = Bits::default();
+    let mut dict = HashMap::new();
+    let mut next = end + 1;
+    let mut width = min as u32 + 1;
+
+    bits.push(clear, width);
+
+    let Some((&first, rest)) = indices.split_first() else {
+        bits.push(end, width);
+        return bits.finish();
+    };
+
+    let mut prefix = first as u16;
+
+    for &k in rest {
+        if let Some(&code) = dict.get(&(prefix, k)) {
+            prefix = code;
+            continue;
+        }
+
+        bits.push(prefix, width);
+        dict.insert((prefix, k), next);
+        next += 1;
+
+        // Decoders widen their codes one entry later, as they lag behind
+        if next > 1 << width && width < 12 {
+            width += 1;
+        }
+
+        if next == MAX {
+            bits.push(clear, width);
+            dict.clear();
+            next = end + 1;
+            width = min as u32 + 1;
+        }
+
+        prefix = k as u16;
+    }
+
+    bits.push(prefix, width);
+    bits.push(end, width);
+    bits.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::Image;
+    use super::*;
+
+    /// Decodes GIF image data, as a viewer would
+    fn unlzw(data: &[u8], min: u8) -> Vec<u8> {
+        let clear = 1usize << min;
+        let mut table = Vec::<Vec<u8>>::new();
+        let mut width = 0;
+        let mut prev: Option<usize> = None;
+        let mut out = Vec::new();
+        let (mut acc, mut len, mut bytes) = (0u32, 0, data.iter());
+
+        loop {
+            if table.is_empty() {
+                table = (0..clear + 2).map(|i| vec![i as u8]).collect();
+                width = min as u32 + 1;
+            }
+
+            while len < width {
+                acc |= (*bytes.next().expect("Missing end code") as u32) << len;
+                len += 8;
+            }
+
+            let code = (acc & ((1 << width) - 1)) as usize;
+
+            acc >>= width;
+            len -= width;
+
+            if code == clear {
+                table.clear();
+                prev = None;
+                continue;
+            }
+
+            if code == clear + 1 {
+                return out;
+            }
+
+            let entry = match prev {
+                None => table[code].clone(),
+                Some(prev) => {
+                    let entry = match table.get(code) {
+                        Some(entry) => entry.clone(),
+                        None => [&table[prev][..], &table[prev][..1]].concat(),
+                    };
+
+                    if table.len() < 4096 {
+                        table.push([&table[prev][..], &entry[..1]].concat());
+                    }
+
+                    entry
+                }
+            };
+
+            if table.len() == 1 << width && width < 12 {
+                width += 1;
+            }
+
+            out.extend(&entry);
+            prev = Some(code);
+        }
+    }
+
+    #[test]
+    fn lzw() {
+        let mut rng = Rng::new(7);
+
+        for (len, colors) in [
+            (0, 2),
+            (1, 2),
+            (100, 4),
+            (5000, 3),
+            (20000, 256),
+            (70000, 16),
+        ] {
+            let min = (colors as u32).next_power_of_two().trailing_zeros().max(2) as u8;
+            let indices = (0..len)
+                .map(|i| match i % 3 {
+                    // Mix runs in, to grow long dictionary entries
+                    0 => (i / 500 % colors) as u8,
+                    _ => rng.below(colors) as u8,
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                unlzw(&super::lzw(&indices, min), min),
+                indices,
+                "{len} {colors}"
+            );
+        }
+    }
+
+    #[test]
+    fn gif() -> Result<(), Error> {
+        let mut out = Vec::new();
+        let mut gif = Gif::new(&mut out, 10, 2);
+        let mut image = Image::new(3, 2, Rgb::BLACK);
+
+        gif.record(&image)?;
+        image.set(Point2::new(1, 1), Rgb(9, 8, 7));
+        gif.record(&image)?;
+
+        assert!(gif.record(&Image::new(2, 2, Rgb::BLACK)).is_err());
+
+        gif.finish()?;
+
+        assert!(out.starts_with(b"GIF89a\x06\0\x04\0"));
+        assert_eq!(out.last(), Some(&0x3b));
+        // One delay of 10 hundredths per frame
+        assert_eq!(
+            out.windows(6)
+                .filter(|w| w == b"\x21\xf9\x04\0\x0a\0")
+                .count(),
+            2
+        );
+        // The second frame's color table
+        assert!(out.windows(7).any(|w| w == b"\x80\0\0\0\x09\x08\x07"));
+
+        assert!(Gif::new(Vec::new(), 30, 1).finish().is_err());
+
+        Ok(())
+    }
+}
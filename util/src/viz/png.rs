@@ -0,0 +1,182 @@
+//! Still images, without compression to keep the writers small
+
+use std::io::Write;
+
+use super::Frame;
+use crate::*;
+
+fn rows(frame: &dyn Frame) -> impl Iterator<Item = Vec<u8>> + '_ {
+    (0..frame.height()).map(move |y| {
+        (0..frame.width())
+            .flat_map(|x| {
+                let p = frame.pixel(x, y);
+
+                [p.0, p.1, p.2]
+            })
+            .collect()
+    })
+}
+
+fn check_size(frame: &dyn Frame) -> Result<(), Error> {
+    if frame.width() == 0 || frame.height() == 0 {
+        bail!("Empty image");
+    }
+
+    Ok(())
+}
+
+/// Writes a binary PPM (P6), which most image viewers can open
+pub fn write_ppm(out: &mut dyn Write, frame: &dyn Frame) -> Result<(), Error> {
+    check_size(frame)?;
+
+    write!(out, "P6\n{} {}\n255\n", frame.width(), frame.height())?;
+
+    for row in rows(frame) {
+        out.write_all(&row)?;
+    }
+
+    Ok(())
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for &b in bytes {
+        crc ^= b as u32;
+
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb88320 & (crc & 1).wrapping_neg());
+        }
+    }
+
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+fn chunk(out: &mut dyn Write, kind: &[u8; 4], data: &[u8]) -> Result<(), Error> {
+    let len = u32::try_from(data.len()).context("PNG chunk too large")?;
+    let crc = crc32(&[&kind[..], data].concat());
+
+    out.write_all(&len.to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    out.write_all(&crc.to_be_bytes())?;
+
+    Ok(())
+}
+
+/// Writes an RGB PNG, its pixels zlib "compressed" in stored blocks
+pub fn write_png(out: &mut dyn Write, frame: &dyn Frame) -> Result<(), Error> {
+    check_size(frame)?;
+
+    let width = u32::try_from(frame.width()).context("Image too wide")?;
+    let height = u32::try_from(frame.height()).context("Image too tall")?;
+
+    // Each row starts with its filter type, none
+    let raw = rows(frame)
+        .flat_map(|row| iter::once(0).chain(row))
+        .collect::<Vec<u8>>();
+
+    let mut zlib = vec![0x78, 0x01];
+    let blocks = raw.chunks(0xffff);
+    let count = blocks.len();
+
+    for (i, block) in blocks.enumerate() {
+        let len = block.len() as u16;
+
+        zlib.push(u8::from(i + 1 == count));
+        zlib.extend(len.to_le_bytes());
+        zlib.extend((!len).to_le_bytes());
+        zlib.extend(block);
+    }
+
+    zlib.extend(adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::new();
+
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // 8 bits per channel, RGB, and the only compression, filter and interlace methods
+    header.extend([8, 2, 0, 0, 0]);
+
+    out.write_all(b"\x89PNG\r\n\x1a\n")?;
+    chunk(out, b"IHDR", &header)?;
+    chunk(out, b"IDAT", &zlib)?;
+    chunk(out, b"IEND", &[])?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{Image, Rgb};
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(super::crc32(b"IEND"), 0xae426082);
+        assert_eq!(super::adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    #[test]
+    fn ppm() -> Result<(), Error> {
+        let mut image = Image::new(2, 1, Rgb::BLACK);
+        let mut out = Vec::new();
+
+        image.set(Point2::new(1, 0), Rgb(1, 2, 3));
+        write_ppm(&mut out, &image)?;
+
+        assert_eq!(out, b"P6\n2 1\n255\n\0\0\0\x01\x02\x03");
+
+        Ok(())
+    }
+
+    #[test]
+    fn png() -> Result<(), Error> {
+        // Large enough to need two stored blocks
+        let image = Image::new(200, 120, Rgb(10, 20, 30));
+        let mut out = Vec::new();
+
+        write_png(&mut out, &image)?;
+
+        assert!(out.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(out.ends_with(b"IEND\xae\x42\x60\x82"));
+        assert_eq!(&out[16..24], [0, 0, 0, 200, 0, 0, 0, 120]);
+
+        // Reassemble the stored blocks
+        let len = u32::from_be_bytes(out[33..37].try_into()?) as usize;
+        let zlib = &out[41..41 + len];
+        let mut raw = Vec::new();
+        let mut i = 2;
+
+        loop {
+            let n = u16::from_le_bytes([zlib[i + 1], zlib[i + 2]]) as usize;
+
+            raw.extend(&zlib[i + 5..i + 5 + n]);
+
+            if zlib[i] == 1 {
+                break;
+            }
+
+            i += 5 + n;
+        }
+
+        assert_eq!(raw.len(), 120 * (1 + 200 * 3));
+        assert_eq!(&raw[..4], [0, 10, 20, 30]);
+        assert_eq!(&zlib[zlib.len() - 4..], super::adler32(&raw).to_be_bytes());
+
+        assert!(write_png(&mut Vec::new(), &Image::new(0, 3, Rgb::BLACK)).is_err());
+
+        Ok(())
+    }
+}